@@ -0,0 +1,160 @@
+use std::collections::{HashMap, VecDeque};
+
+// Pattern `pattern` found over the chars `start..end` of the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Default)]
+struct Node {
+    next: HashMap<char, usize>,
+    fail: usize,
+    depth: usize,
+    // The pattern spelled by the path to this node, if any.
+    pattern: Option<usize>,
+    // The nearest node along the fail links that ends a pattern.
+    output: Option<usize>,
+}
+
+// A trie of all patterns with fail links, so one pass over a text finds every match,
+// overlapping ones included. Works on chars, not bytes.
+pub struct AhoCorasick {
+    nodes: Vec<Node>,
+    longest: usize,
+}
+
+impl AhoCorasick {
+    // Empty patterns are ignored; of two equal patterns only the first is reported.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let mut nodes = vec![Node::default()];
+        let mut longest = 0;
+        for (index, pattern) in patterns.iter().enumerate() {
+            let pattern = pattern.as_ref();
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut node = 0;
+            for c in pattern.chars() {
+                node = match nodes[node].next.get(&c) {
+                    Some(&next) => next,
+                    None => {
+                        let depth = nodes[node].depth + 1;
+                        nodes.push(Node { depth, ..Node::default() });
+                        let next = nodes.len() - 1;
+                        nodes[node].next.insert(c, next);
+                        next
+                    }
+                };
+            }
+            nodes[node].pattern.get_or_insert(index);
+            longest = longest.max(nodes[node].depth);
+        }
+
+        // Breadth first, so the fail links of shallower nodes are known when they are needed.
+        let mut queue: VecDeque<usize> = nodes[0].next.values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<(char, usize)> = nodes[node].next.iter().map(|(&c, &child)| (c, child)).collect();
+            for (c, child) in children {
+                let mut fail = nodes[node].fail;
+                let target = loop {
+                    if let Some(&target) = nodes[fail].next.get(&c) {
+                        break target;
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = nodes[fail].fail;
+                };
+                nodes[child].fail = target;
+                nodes[child].output = if nodes[target].pattern.is_some() { Some(target) } else { nodes[target].output };
+                queue.push_back(child);
+            }
+        }
+
+        Self { nodes, longest }
+    }
+
+    fn step(&self, mut node: usize, c: char) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[node].next.get(&c) {
+                return next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    // Every match, in the order their ends are reached.
+    pub fn find_iter<I: Iterator<Item = char>>(&self, text: I) -> FindIter<'_, I> {
+        FindIter {
+            automaton: self,
+            text,
+            node: 0,
+            position: 0,
+            pending: None,
+        }
+    }
+
+    // The match that starts first, and of those the longest. Stops reading `text` as soon as
+    // no later match can start any earlier.
+    pub fn leftmost_longest(&self, text: impl Iterator<Item = char>) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        let mut node = 0;
+        for (position, c) in text.enumerate() {
+            if best.is_some_and(|best| best.start + self.longest <= position) {
+                break;
+            }
+
+            node = self.step(node, c);
+            let mut output = if self.nodes[node].pattern.is_some() { Some(node) } else { self.nodes[node].output };
+            while let Some(found) = output {
+                let found = &self.nodes[found];
+                let start = position + 1 - found.depth;
+                if best.is_none_or(|best| start < best.start || (start == best.start && position + 1 > best.end)) {
+                    best = found.pattern.map(|pattern| Match { pattern, start, end: position + 1 });
+                }
+                output = found.output;
+            }
+        }
+
+        best
+    }
+}
+
+pub struct FindIter<'a, I> {
+    automaton: &'a AhoCorasick,
+    text: I,
+    node: usize,
+    position: usize,
+    // A node ending a pattern at `position` that has not been reported yet.
+    pending: Option<usize>,
+}
+
+impl<I: Iterator<Item = char>> Iterator for FindIter<'_, I> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        loop {
+            if let Some(found) = self.pending {
+                let found = &self.automaton.nodes[found];
+                self.pending = found.output;
+                return found.pattern.map(|pattern| Match {
+                    pattern,
+                    start: self.position - found.depth,
+                    end: self.position,
+                });
+            }
+
+            self.node = self.automaton.step(self.node, self.text.next()?);
+            self.position += 1;
+            let node = &self.automaton.nodes[self.node];
+            self.pending = if node.pattern.is_some() { Some(self.node) } else { node.output };
+        }
+    }
+}
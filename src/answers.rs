@@ -0,0 +1,241 @@
+use crate::solution::Answer;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+// Records under this hash hold for every input of that day and part.
+pub const ANY_INPUT: &str = "*";
+
+// Answers known to be wrong before the store existed, so they were never keyed by an input hash.
+// They are checked after the store's own records and never saved with them.
+const KNOWN: &str = "\
+[day24.part1.*]
+rejected = [13135, 26270]
+";
+
+// FNV-1a, so the hash of an input stays the same across Rust releases.
+pub fn input_hash(input: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub day: u32,
+    pub part: u8,
+    pub input_hash: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Record {
+    pub accepted: Option<i128>,
+    pub rejected: Vec<i128>,
+    pub too_low: Option<i128>,
+    pub too_high: Option<i128>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    Accepted,
+    Rejected,
+    TooLow,
+    TooHigh,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch(String),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Match => write!(f, "MATCH"),
+            Verdict::Mismatch(reason) => write!(f, "MISMATCH ({})", reason),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+impl Record {
+    pub fn check(&self, answer: i128) -> Verdict {
+        if let Some(accepted) = self.accepted {
+            if accepted == answer {
+                return Verdict::Match;
+            }
+            return Verdict::Mismatch(format!("accepted answer is {}", accepted));
+        }
+        if self.rejected.contains(&answer) {
+            return Verdict::Mismatch("rejected before".to_string());
+        }
+        if let Some(too_low) = self.too_low {
+            if answer <= too_low {
+                return Verdict::Mismatch(format!("{} was too low", too_low));
+            }
+        }
+        if let Some(too_high) = self.too_high {
+            if answer >= too_high {
+                return Verdict::Mismatch(format!("{} was too high", too_high));
+            }
+        }
+
+        Verdict::Unknown
+    }
+
+    pub fn mark(&mut self, mark: Mark, answer: i128) {
+        match mark {
+            Mark::Accepted => self.accepted = Some(answer),
+            Mark::Rejected => {
+                if !self.rejected.contains(&answer) {
+                    self.rejected.push(answer);
+                    self.rejected.sort();
+                }
+            }
+            Mark::TooLow => self.too_low = Some(self.too_low.map_or(answer, |low| low.max(answer))),
+            Mark::TooHigh => self.too_high = Some(self.too_high.map_or(answer, |high| high.min(answer))),
+        }
+    }
+}
+
+// Accepted and rejected answers, stored as a small TOML file:
+//
+//   [day24.part1.0123456789abcdef]
+//   too_low = 13135
+//   too_high = 26270
+pub struct AnswerStore {
+    path: PathBuf,
+    records: BTreeMap<Key, Record>,
+    known: BTreeMap<Key, Record>,
+}
+
+impl AnswerStore {
+    // A missing file is an empty store; it is created on the first `save`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(format!("cannot read {}: {}", path.display(), err)),
+        };
+
+        let records = parse_records(&content).map_err(|err| format!("{}:{}", path.display(), err))?;
+        let known = parse_records(KNOWN).expect("known answers should parse");
+        Ok(Self { path, records, known })
+    }
+
+    pub fn check(&self, day: u32, part: u8, input_hash: &str, answer: Answer) -> Verdict {
+        let key = |input_hash: &str| Key {
+            day,
+            part,
+            input_hash: input_hash.to_string(),
+        };
+        let records = [
+            self.records.get(&key(input_hash)),
+            self.records.get(&key(ANY_INPUT)),
+            self.known.get(&key(ANY_INPUT)),
+        ];
+        records
+            .into_iter()
+            .flatten()
+            .map(|record| record.check(answer.value()))
+            .find(|verdict| *verdict != Verdict::Unknown)
+            .unwrap_or(Verdict::Unknown)
+    }
+
+    pub fn mark(&mut self, day: u32, part: u8, input_hash: &str, mark: Mark, answer: i128) {
+        let key = Key {
+            day,
+            part,
+            input_hash: input_hash.to_string(),
+        };
+        self.records.entry(key).or_default().mark(mark, answer);
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        fs::write(&self.path, self.to_toml()).map_err(|err| format!("cannot write {}: {}", self.path.display(), err))
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut out = String::from("# Puzzle answers keyed by day, part and input hash.\n");
+        for (key, record) in &self.records {
+            out += &format!("\n[day{}.part{}.{}]\n", key.day, key.part, key.input_hash);
+            if let Some(accepted) = record.accepted {
+                out += &format!("accepted = {}\n", accepted);
+            }
+            if !record.rejected.is_empty() {
+                let rejected: Vec<String> = record.rejected.iter().map(|x| x.to_string()).collect();
+                out += &format!("rejected = [{}]\n", rejected.join(", "));
+            }
+            if let Some(too_low) = record.too_low {
+                out += &format!("too_low = {}\n", too_low);
+            }
+            if let Some(too_high) = record.too_high {
+                out += &format!("too_high = {}\n", too_high);
+            }
+        }
+        out
+    }
+}
+
+fn parse_records(content: &str) -> Result<BTreeMap<Key, Record>, String> {
+    let mut records: BTreeMap<Key, Record> = BTreeMap::new();
+    let mut current: Option<Key> = None;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |expected: &str| format!("{}: expected {}, found `{}`", index + 1, expected, raw_line.trim());
+
+        if let Some(header) = line.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+            let parts = header.split('.').collect::<Vec<&str>>();
+            let key = match parts.as_slice() {
+                [day, part, input_hash] => Key {
+                    day: day.strip_prefix("day").and_then(|x| x.parse().ok()).ok_or_else(|| error("`dayN`"))?,
+                    part: part.strip_prefix("part").and_then(|x| x.parse().ok()).ok_or_else(|| error("`partN`"))?,
+                    input_hash: input_hash.to_string(),
+                },
+                _ => return Err(error("`[dayN.partN.HASH]`")),
+            };
+            records.entry(key.clone()).or_default();
+            current = Some(key);
+            continue;
+        }
+
+        let key = current.as_ref().ok_or_else(|| error("a `[dayN.partN.HASH]` header"))?;
+        let record = records.get_mut(key).expect("header inserts the record");
+        let (name, value) = line.split_once('=').ok_or_else(|| error("`key = value`"))?;
+        let number = |value: &str| value.trim().parse::<i128>().map_err(|_| error("a number"));
+        match name.trim() {
+            "accepted" => record.accepted = Some(number(value)?),
+            "too_low" => record.too_low = Some(number(value)?),
+            "too_high" => record.too_high = Some(number(value)?),
+            "rejected" => {
+                let list = value
+                    .trim()
+                    .strip_prefix('[')
+                    .and_then(|x| x.strip_suffix(']'))
+                    .ok_or_else(|| error("a list of numbers"))?;
+                record.rejected = list
+                    .split(',')
+                    .filter(|x| !x.trim().is_empty())
+                    .map(number)
+                    .collect::<Result<Vec<i128>, String>>()?;
+            }
+            _ => return Err(error("`accepted`, `rejected`, `too_low` or `too_high`")),
+        }
+    }
+
+    Ok(records)
+}
@@ -0,0 +1,120 @@
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_PATH: &str = "benchmarks.csv";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let runs = samples.len();
+        if runs == 0 {
+            return Self {
+                runs,
+                mean: Duration::ZERO,
+                median: Duration::ZERO,
+                stddev: Duration::ZERO,
+            };
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let median = if runs % 2 == 1 {
+            sorted[runs / 2]
+        } else {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        };
+
+        let mean_secs = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean_secs).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Self {
+            runs,
+            mean: Duration::from_secs_f64(mean_secs),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+pub fn measure<T>(runs: usize, mut f: impl FnMut() -> T) -> Stats {
+    let samples: Vec<Duration> = (0..runs).map(|_| time(|| black_box(f())).1).collect();
+    Stats::from_samples(&samples)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchResult {
+    pub day: u32,
+    pub name: String,
+    pub stats: Stats,
+}
+
+// Results of an earlier run; a missing file means there is nothing to compare against.
+pub fn load_previous(path: impl AsRef<Path>) -> Result<Vec<BenchResult>, String> {
+    let path = path.as_ref();
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(format!("cannot read {}: {}", path.display(), err)),
+    };
+
+    let mut previous = Vec::new();
+    for (index, line) in content.lines().enumerate().skip(1) {
+        let error = || format!("{}:{}: expected `day,name,runs,mean_ns,median_ns,stddev_ns`", path.display(), index + 1);
+        let fields = line.split(',').collect::<Vec<&str>>();
+        let [day, name, runs, mean, median, stddev] = fields.as_slice() else {
+            return Err(error());
+        };
+        let nanos = |value: &str| value.parse::<u64>().map(Duration::from_nanos).map_err(|_| error());
+
+        previous.push(BenchResult {
+            day: day.parse::<u32>().map_err(|_| error())?,
+            name: name.to_string(),
+            stats: Stats {
+                runs: runs.parse::<usize>().map_err(|_| error())?,
+                mean: nanos(mean)?,
+                median: nanos(median)?,
+                stddev: nanos(stddev)?,
+            },
+        });
+    }
+
+    Ok(previous)
+}
+
+pub fn save(path: impl AsRef<Path>, results: &[BenchResult]) -> Result<(), String> {
+    let mut out = String::from("day,name,runs,mean_ns,median_ns,stddev_ns\n");
+    for result in results {
+        out += &format!(
+            "{},{},{},{},{},{}\n",
+            result.day,
+            result.name,
+            result.stats.runs,
+            result.stats.mean.as_nanos(),
+            result.stats.median.as_nanos(),
+            result.stats.stddev.as_nanos()
+        );
+    }
+
+    let path = path.as_ref();
+    fs::write(path, out).map_err(|err| format!("cannot write {}: {}", path.display(), err))
+}
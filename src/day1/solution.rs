@@ -1,142 +1,240 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::aho_corasick::AhoCorasick;
+use crate::day1::vocabulary::Vocabulary;
+use crate::parse::{read_file, read_lines, ParseError};
+use crate::solution::{Answer, Effect, Failure, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
+
+// A digit over the chars `start..end` of a line, written as a symbol or spelled out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Digit {
+    pub value: u8,
+    pub start: usize,
+    pub end: usize,
+    pub spelled: bool,
+}
+
+// How one line decodes; `digits` is `None` for a line without any digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibration {
+    pub line: usize,
+    pub digits: Option<(Digit, Digit)>,
+}
+
+impl Calibration {
+    pub fn value(&self) -> Option<u64> {
+        self.digits.map(|(first, last)| (first.value * 10 + last.value) as u64)
+    }
+}
+
+// The digit symbols and words compiled once in both directions: the first digit is the leftmost
+// match in the line, the last one the leftmost match in the reversed line. Overlaps such as
+// "eightwo" count both ways, and at the same position the longer word wins.
+struct Digits {
+    forward: AhoCorasick,
+    backward: AhoCorasick,
+    // Value and whether it is spelled out, by pattern.
+    digits: Vec<(u8, bool)>,
+}
+
+impl Digits {
+    fn new(vocabulary: &Vocabulary, full_width: bool) -> Self {
+        let mut symbols: Vec<(String, (u8, bool))> = (0..=9).map(|digit| (digit.to_string(), (digit, false))).collect();
+        if full_width {
+            symbols.extend(('０'..='９').zip(0..).map(|(symbol, digit)| (symbol.to_string(), (digit, false))));
+        }
+        let words = vocabulary.words().map(|(word, digit)| (word.to_string(), (digit, true)));
+        let patterns: Vec<(String, (u8, bool))> = symbols.into_iter().chain(words).collect();
+        let reversed: Vec<String> = patterns.iter().map(|(word, _)| word.chars().rev().collect()).collect();
+
+        Self {
+            forward: AhoCorasick::new(&patterns.iter().map(|(word, _)| word.as_str()).collect::<Vec<&str>>()),
+            backward: AhoCorasick::new(&reversed),
+            digits: patterns.iter().map(|(_, digit)| *digit).collect(),
+        }
+    }
+
+    fn first_and_last(&self, line: &str) -> Option<(Digit, Digit)> {
+        let first = self.forward.leftmost_longest(line.chars())?;
+        let last = self.backward.leftmost_longest(line.chars().rev())?;
+        let len = line.chars().count();
+
+        let digit = |pattern: usize, start: usize, end: usize| {
+            let (value, spelled) = self.digits[pattern];
+            Digit { value, start, end, spelled }
+        };
+        Some((
+            digit(first.pattern, first.start, first.end),
+            digit(last.pattern, len - last.end, len - last.start),
+        ))
+    }
+}
 
 pub struct Day {
-    number_strings: [&'static str; 9],
-    number_symbols: [&'static str; 9],
-    input: Vec<String>,
+    vocabulary: Vocabulary,
+    // Whether `０` to `９` count as digits too.
+    full_width: bool,
+    symbols: Digits,
+    words: Digits,
+    // Non-empty lines with their line numbers.
+    input: Vec<(usize, String)>,
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).expect("should be able to open the file");
-        let reader = BufReader::new(file);
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
 
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
         let mut input_vec = Vec::new();
-        for line in reader.lines() {
-            input_vec.push(line.expect("should be able to read the string"))
+        for (index, line) in read_lines(reader)?.into_iter().enumerate() {
+            if !line.is_empty() {
+                input_vec.push((index + 1, line))
+            }
         }
 
-        Self {
-            number_strings: ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"],
-            number_symbols: ["1", "2", "3", "4", "5", "6", "7", "8", "9"],
+        Ok(Self {
+            vocabulary: Vocabulary::default(),
+            full_width: false,
+            symbols: Digits::new(&Vocabulary::empty(), false),
+            words: Digits::new(&Vocabulary::default(), false),
             input: input_vec,
-        }
+        })
+    }
+
+    pub fn vocabulary(&self) -> &Vocabulary {
+        &self.vocabulary
+    }
+
+    pub fn set_vocabulary(&mut self, vocabulary: Vocabulary) {
+        self.words = Digits::new(&vocabulary, self.full_width);
+        self.vocabulary = vocabulary;
+    }
+
+    pub fn set_full_width(&mut self, full_width: bool) {
+        self.full_width = full_width;
+        self.symbols = Digits::new(&Vocabulary::empty(), full_width);
+        self.words = Digits::new(&self.vocabulary, full_width);
     }
 
+    // Every line as `part` reads it: symbols only for part 1, spelled digits too for part 2.
+    pub fn calibrations(&self, part: u8) -> Vec<Calibration> {
+        let digits = if part == 1 { &self.symbols } else { &self.words };
+        self.input
+            .iter()
+            .map(|(line, text)| Calibration { line: *line, digits: digits.first_and_last(text) })
+            .collect()
+    }
+
+    // Lines without a digit add nothing; `report` and `warnings` list them.
     pub fn solve(&self) -> u64 {
-        let mut total: u64 = Default::default();
-        for line in &self.input {
-            let chars = line.split("").filter(|&x| !x.is_empty()).collect::<Vec<&str>>();
-            let mut left = 0;
-            let mut right = line.len() - 1;
-            let mut number_str: String = Default::default();
-
-            while left < line.len() {
-                let left_res = chars[left].parse::<u64>();
-                match left_res {
-                    Ok(_) => {
-                        number_str.push_str(chars[left]);
-                        break;
-                    }
-                    _ => {
-                        left += 1;
-                    }
-                };
-            }
+        self.calibrations(1).iter().filter_map(Calibration::value).sum()
+    }
 
-            while right >= 0 {
-                let right_res = chars[right].parse::<u64>();
-                match right_res {
-                    Ok(_) => {
-                        number_str.push_str(chars[right]);
-                        break;
-                    }
-                    _ => {
-                        if right == 0 {
-                            break;
-                        }
-                        right -= 1;
-                    }
-                };
-            }
+    pub fn solve2(&self) -> u64 {
+        self.calibrations(2).iter().filter_map(Calibration::value).sum()
+    }
 
-            let number = number_str.parse::<u64>().unwrap();
-            total += number;
+    fn describe(&self, part: u8) -> String {
+        let mut report = String::new();
+        let mut missing = 0;
+        for (calibration, (_, text)) in self.calibrations(part).iter().zip(&self.input) {
+            let Some((first, last)) = calibration.digits else {
+                report += &format!("line {} `{}`: no digit\n", calibration.line, text);
+                missing += 1;
+                continue;
+            };
+
+            let describe = |digit: Digit| {
+                let written: String = text.chars().skip(digit.start).take(digit.end - digit.start).collect();
+                let kind = if digit.spelled { "spelled" } else { "symbol" };
+                format!("{} at column {} ({} `{}`)", digit.value, digit.start + 1, kind, written)
+            };
+            report += &format!(
+                "line {} `{}`: {} = first {}, last {}\n",
+                calibration.line,
+                text,
+                calibration.value().unwrap_or_default(),
+                describe(first),
+                describe(last)
+            );
         }
+        if missing > 0 {
+            report += &format!(
+                "{} of {} non-blank lines have no digit and are left out of the total (blank lines are skipped)\n",
+                missing,
+                self.input.len()
+            );
+        }
+        report
+    }
+}
+
+impl FromStr for Day {
+    type Err = ParseError;
 
-        total
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
     }
+}
 
-    pub fn solve2(&self) -> u64 {
-        let mut total: u64 = Default::default();
-        for line in &self.input {
-            let chars = line.split("").filter(|&x| !x.is_empty()).collect::<Vec<&str>>();
-            let mut left = 0;
-            let mut right = line.len() - 1;
-            let mut number_str: String = Default::default();
-
-            while left < line.len() {
-                let left_res = chars[left].parse::<u64>();
-                match left_res {
-                    Ok(_) => {
-                        break;
-                    }
-                    _ => {
-                        left += 1;
-                    }
-                };
-            }
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
 
-            while right >= 0 {
-                let right_res = chars[right].parse::<u64>();
-                match right_res {
-                    Ok(_) => {
-                        break;
-                    }
-                    _ => {
-                        if right == 0 {
-                            break;
-                        }
-                        right -= 1;
-                    }
-                };
-            }
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
 
-            let mut left_hardcoded_index = line.len();
-            let mut right_hardcoded_index = 0;
-            let mut left_hardcoded_number = "";
-            let mut right_hardcoded_number = "";
-            for (index, item) in self.number_strings.iter().enumerate() {
-                let finding: Vec<_> = line.match_indices(item).collect();
-                for (i_finding, _) in finding {
-                    if i_finding < left_hardcoded_index {
-                        left_hardcoded_index = i_finding;
-                        left_hardcoded_number = self.number_symbols[index]
-                    }
-                    if i_finding > right_hardcoded_index {
-                        right_hardcoded_index = i_finding;
-                        right_hardcoded_number = self.number_symbols[index]
-                    }
-                }
-            }
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
+    }
 
-            if left_hardcoded_index < left {
-                number_str.push_str(left_hardcoded_number);
-            } else {
-                number_str.push_str(chars[left]);
+    // A total that silently skips lines should not pass for the answer.
+    fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for part in [1, 2] {
+            let missing: Vec<String> = self
+                .calibrations(part)
+                .iter()
+                .filter(|calibration| calibration.digits.is_none())
+                .map(|calibration| calibration.line.to_string())
+                .collect();
+            if !missing.is_empty() {
+                warnings.push(format!(
+                    "part {}: line(s) {} have no digit and are left out of the total",
+                    part,
+                    missing.join(", ")
+                ));
             }
+        }
+        warnings
+    }
 
-            if right_hardcoded_index > right {
-                number_str.push_str(right_hardcoded_number);
-            } else {
-                number_str.push_str(chars[right]);
-            }
+    fn report(&self) -> Option<String> {
+        Some(format!("part 1:\n{}part 2:\n{}", self.describe(1), self.describe(2)))
+    }
 
-            let number = number_str.parse::<u64>().unwrap();
-            total += number;
+    // `words=english,zero` picks built-in vocabularies, `words-file=PATH` adds `word=digit` lines
+    // and `full-width=true` accepts `０` to `９` as digits.
+    fn configure(&mut self, key: &str, value: &str) -> Result<Effect, String> {
+        match key {
+            "words" => {
+                self.set_vocabulary(Vocabulary::from_names(value)?);
+                Ok(Effect::ChangesAnswers)
+            }
+            "words-file" => {
+                let mut vocabulary = self.vocabulary.clone();
+                vocabulary.extend(&Vocabulary::load(value).map_err(|err| err.to_string())?);
+                self.set_vocabulary(vocabulary);
+                Ok(Effect::ChangesAnswers)
+            }
+            "full-width" => {
+                self.set_full_width(value.parse().map_err(|_| format!("`{}` is not true or false", value))?);
+                Ok(Effect::ChangesAnswers)
+            }
+            _ => Err(format!("unknown option `{}`, expected words, words-file or full-width", key)),
         }
-
-        total
     }
 }
@@ -0,0 +1,105 @@
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use std::io::BufRead;
+
+// Vocabularies that can be named in `--opt words=...`, each listing the words for 1 to 9
+// (or 0 and up, for `zero`).
+const BUILT_IN: [(&str, &[&str]); 6] = [
+    ("english", &["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"]),
+    ("german", &["eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"]),
+    ("french", &["un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"]),
+    ("spanish", &["uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve"]),
+    ("roman", &["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"]),
+    ("zero", &["zero"]),
+];
+
+// The spelled-out words a calibration line may use for its digits. Matching is case sensitive,
+// so roman numerals only match in upper case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u8)>,
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Self::named("english").expect("english is built in")
+    }
+}
+
+impl Vocabulary {
+    pub fn empty() -> Self {
+        Self { words: Vec::new() }
+    }
+
+    pub fn built_in() -> impl Iterator<Item = &'static str> {
+        BUILT_IN.iter().map(|(name, _)| *name)
+    }
+
+    pub fn named(name: &str) -> Option<Self> {
+        let (name, words) = BUILT_IN.iter().find(|(built_in, _)| *built_in == name)?;
+        let first = if *name == "zero" { 0 } else { 1 };
+        Some(Self {
+            words: words.iter().zip(first..).map(|(word, digit)| (word.to_string(), digit)).collect(),
+        })
+    }
+
+    // E.g. `english,zero`: the words of all listed vocabularies together.
+    pub fn from_names(names: &str) -> Result<Self, String> {
+        let mut vocabulary = Self::empty();
+        for name in names.split(',').map(str::trim) {
+            let named = Self::named(name).ok_or(format!(
+                "no vocabulary called `{}`, expected one of {}",
+                name,
+                Self::built_in().collect::<Vec<_>>().join(", ")
+            ))?;
+            vocabulary.extend(&named);
+        }
+        Ok(vocabulary)
+    }
+
+    pub fn load(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
+
+    // One `word=digit` pair per line; blank lines and lines starting with `#` are skipped.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        let mut vocabulary = Self::empty();
+        for (index, text) in read_lines(reader)?.iter().enumerate() {
+            let line = Line { number: index + 1, text };
+            let trimmed = text.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let (word, digit) = trimmed.split_once('=').ok_or_else(|| line.error(trimmed, "`word=digit`"))?;
+            let word = word.trim();
+            if word.is_empty() {
+                return Err(line.error(word, "a word before `=`"));
+            }
+            let digit = line
+                .number::<u8>(digit)
+                .ok()
+                .filter(|digit| *digit <= 9)
+                .ok_or_else(|| line.error(digit.trim(), "a digit from 0 to 9"))?;
+            vocabulary.insert(word, digit);
+        }
+        Ok(vocabulary)
+    }
+
+    // A word that is already known takes the new digit.
+    pub fn insert(&mut self, word: &str, digit: u8) {
+        match self.words.iter_mut().find(|(known, _)| known == word) {
+            Some(known) => known.1 = digit,
+            None => self.words.push((word.to_string(), digit)),
+        }
+    }
+
+    pub fn extend(&mut self, other: &Vocabulary) {
+        for (word, digit) in &other.words {
+            self.insert(word, *digit);
+        }
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.words.iter().map(|(word, digit)| (word.as_str(), *digit))
+    }
+}
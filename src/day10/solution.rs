@@ -1,170 +1,225 @@
+use crate::grid::{Direction, Grid, Pos};
+use crate::parse::{read_file, Line, ParseError};
+use crate::solution::{Answer, Failure, Solution, Variant};
 use std::cmp;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use std::str::FromStr;
 
 pub struct Day {
-    map: Vec<Vec<char>>,
-    start_position: (usize, usize),
+    map: Grid<char>,
+    start_position: Pos,
+    // The pipe hidden under `S`.
+    start_shape: char,
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).unwrap();
-        let reader = BufReader::new(file);
-        let lines = reader.lines();
-
-        let mut map: Vec<Vec<char>> = Vec::new();
-        let mut start_position = (0, 0);
-        let mut y: usize = Default::default();
-        for line_res in lines {
-            let line = line_res.unwrap();
-            let parts: Vec<char> = line.chars().collect();
-            map.push(parts.clone());
-
-            if let Some(x) = parts.iter().position(|&x| x == 'S') {
-                start_position = (y, x);
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        let mut last_line: (usize, String) = (1, String::new());
+        // Line number, text and byte offset of the `S`.
+        let mut start_line: Option<(usize, String, usize)> = None;
+        let mut starts = 0;
+        let map = Grid::from_reader(reader, |line: &Line, tile: &str| {
+            last_line = (line.number, line.text.to_string());
+            if !"|-LJ7F.S".contains(tile) {
+                return Err(line.error(tile, "a pipe out of `|-LJ7F.S`"));
             }
-            y += 1;
-        }
+            if tile == "S" {
+                starts += 1;
+                if starts > 1 {
+                    return Err(line.error(tile, "a single starting tile"));
+                }
+                let offset = tile.as_ptr() as usize - line.text.as_ptr() as usize;
+                start_line = Some((line.number, line.text.to_string(), offset));
+            }
+            Ok(tile.chars().next().unwrap_or_default())
+        })?;
+
+        let start_position = map.find(|&tile| tile == 'S').ok_or_else(|| {
+            let line = Line { number: last_line.0, text: &last_line.1 };
+            line.error("", "a starting tile `S`")
+        })?;
 
-        Day {
+        let mut day = Day {
             map,
             start_position,
-        }
+            start_shape: 'S',
+        };
+        day.start_shape = day.closing_shape().ok_or_else(|| {
+            let (number, text, offset) = start_line.expect("the start was found");
+            let line = Line { number, text: &text };
+            line.error(&text[offset..offset + 1], "a starting tile on a closed loop of pipes")
+        })?;
+        Ok(day)
     }
 
-    pub fn solve(&self) -> u64 {
-        let mut neighbors: Vec<((usize, usize), char)> = Vec::new();
-        if self.start_position.0 > 0
-            && !vec!['L', 'J', '-', '.']
-                .contains(&self.map[self.start_position.0 - 1][self.start_position.1])
-        {
-            neighbors.push(((self.start_position.0 - 1, self.start_position.1), 'u'));
-        }
-        if self.start_position.0 < self.map.len() - 1
-            && !vec!['F', '7', '-', '.']
-                .contains(&self.map[self.start_position.0 + 1][self.start_position.1])
-        {
-            neighbors.push(((self.start_position.0 + 1, self.start_position.1), 'd'));
-        }
-        if self.start_position.1 > 0
-            && !vec!['7', 'J', '|', '.']
-                .contains(&self.map[self.start_position.0][self.start_position.1 - 1])
-        {
-            neighbors.push(((self.start_position.0, self.start_position.1 - 1), 'l'));
-        }
-        if self.start_position.1 < self.map[0].len() - 1
-            && !vec!['F', 'L', '|', '.']
-                .contains(&self.map[self.start_position.0][self.start_position.1 + 1])
-        {
-            neighbors.push(((self.start_position.0, self.start_position.1 + 1), 'r'));
+    // The two directions a pipe leads to.
+    fn connections(pipe: char) -> Option<[Direction; 2]> {
+        match pipe {
+            '|' => Some([Direction::Up, Direction::Down]),
+            '-' => Some([Direction::Left, Direction::Right]),
+            'L' => Some([Direction::Up, Direction::Right]),
+            'J' => Some([Direction::Up, Direction::Left]),
+            '7' => Some([Direction::Down, Direction::Left]),
+            'F' => Some([Direction::Down, Direction::Right]),
+            _ => None,
         }
+    }
 
+    pub fn solve(&self) -> u64 {
         let mut max_count: u64 = Default::default();
-        let mut already_traversed_neighbor_dir: Vec<char> = Vec::new();
-        for neighbor in neighbors {
-            if already_traversed_neighbor_dir.contains(&neighbor.1) {
+        let mut already_traversed_neighbor_dir: Vec<Direction> = Vec::new();
+        for dir in Direction::ALL {
+            if already_traversed_neighbor_dir.contains(&dir) {
                 continue;
             }
 
             let mut count: u64 = Default::default();
-            let mut next_pos_and_dir: ((usize, usize), char) = neighbor;
-            let mut stop = false;
-            while !stop {
-                count += 1;
-
-                next_pos_and_dir = self.next_position(
-                    next_pos_and_dir,
-                    self.map[next_pos_and_dir.0 .0][next_pos_and_dir.0 .1],
-                );
-                let (next_pos, next_dir) = next_pos_and_dir;
-
-                stop = (next_dir == 's') || (self.map[next_pos.0][next_pos.1] == 'S');
-
-                if self.map[next_pos.0][next_pos.1] == 'S' {
-                    match next_dir {
-                        'r' => already_traversed_neighbor_dir.push('l'),
-                        'l' => already_traversed_neighbor_dir.push('r'),
-                        'u' => already_traversed_neighbor_dir.push('d'),
-                        'd' => already_traversed_neighbor_dir.push('u'),
-                        _ => {
-                            panic!("Unexpected direction");
-                        }
-                    }
+            let mut next_pos_and_dir = self.map.step(self.start_position, dir).map(|pos| (pos, dir));
+            while let Some((pos, next_dir)) = next_pos_and_dir {
+                if self.map[pos] == 'S' {
+                    already_traversed_neighbor_dir.push(next_dir.opposite());
+                    max_count = cmp::max(max_count, count);
+                    break;
                 }
 
-                already_traversed_neighbor_dir.push(neighbor.1);
+                count += 1;
+                next_pos_and_dir = self.next_position(pos, next_dir);
             }
 
-            max_count = cmp::max(max_count, count);
+            already_traversed_neighbor_dir.push(dir);
         }
 
         (max_count as f64 / 2.0).ceil() as u64
     }
 
-    fn next_position(&self, node: ((usize, usize), char), pipe: char) -> ((usize, usize), char) {
-        let ((y, x), dir) = node;
+    // Follows the pipe at `pos`, entered while moving in `dir`; `None` once the path breaks off.
+    fn next_position(&self, pos: Pos, dir: Direction) -> Option<(Pos, Direction)> {
+        let [a, b] = Self::connections(self.map[pos])?;
+        let next_dir = if a == dir.opposite() {
+            b
+        } else if b == dir.opposite() {
+            a
+        } else {
+            return None;
+        };
+
+        self.map.step(pos, next_dir).map(|next| (next, next_dir))
+    }
 
-        let mut pos_and_dir: ((i32, i32), char) = Default::default();
-        match pipe {
-            'F' => match dir {
-                'l' => pos_and_dir = (((y + 1) as i32, x as i32), 'd'),
-                'u' => pos_and_dir = ((y as i32, (x + 1) as i32), 'r'),
-                _ => {
-                    panic!("Unexpected direction");
-                }
-            },
-            '7' => match dir {
-                'r' => pos_and_dir = (((y + 1) as i32, x as i32), 'd'),
-                'u' => pos_and_dir = ((y as i32, (x - 1) as i32), 'l'),
-                _ => {
-                    panic!("Unexpected direction");
-                }
-            },
-            'J' => match dir {
-                'r' => pos_and_dir = (((y - 1) as i32, x as i32), 'u'),
-                'd' => pos_and_dir = ((y as i32, (x - 1) as i32), 'l'),
-                _ => {
-                    panic!("Unexpected direction");
+    // The pipe hidden under `S`. More than two neighbours may connect to it, so this follows the
+    // pipes from each of them until one path leads back to `S`; the pipe joins its two ends.
+    fn closing_shape(&self) -> Option<char> {
+        for dir in Direction::ALL {
+            let mut next_pos_and_dir = self.map.step(self.start_position, dir).map(|pos| (pos, dir));
+            while let Some((pos, next_dir)) = next_pos_and_dir {
+                if pos == self.start_position {
+                    let ends = [dir, next_dir.opposite()];
+                    return "|-LJ7F".chars().find(|&pipe| {
+                        Self::connections(pipe).is_some_and(|connections| ends.iter().all(|end| connections.contains(end)))
+                    });
                 }
-            },
-            'L' => match dir {
-                'l' => pos_and_dir = (((y - 1) as i32, x as i32), 'u'),
-                'd' => pos_and_dir = ((y as i32, (x + 1) as i32), 'r'),
-                _ => {
-                    panic!("Unexpected direction");
-                }
-            },
-            '|' => match dir {
-                'u' => pos_and_dir = (((y - 1) as i32, x as i32), 'u'),
-                'd' => pos_and_dir = (((y + 1) as i32, x as i32), 'd'),
-                _ => {
-                    panic!("Unexpected direction");
-                }
-            },
-            '-' => match dir {
-                'r' => pos_and_dir = ((y as i32, (x + 1) as i32), 'r'),
-                'l' => pos_and_dir = ((y as i32, (x - 1) as i32), 'l'),
-                _ => {
-                    panic!("Unexpected direction");
-                }
-            },
-            _ => {
-                panic!("Unexpected direction");
+                next_pos_and_dir = self.next_position(pos, next_dir);
+            }
+        }
+
+        None
+    }
+
+    pub fn start_shape(&self) -> char {
+        self.start_shape
+    }
+
+    // The tiles of the loop in walking order, starting at `S`.
+    pub fn loop_tiles(&self) -> Vec<Pos> {
+        let [dir, _] = Self::connections(self.start_shape).expect("the start shape is a pipe");
+
+        let mut tiles = vec![self.start_position];
+        let mut next_pos_and_dir = self.map.step(self.start_position, dir).map(|pos| (pos, dir));
+        while let Some((pos, next_dir)) = next_pos_and_dir {
+            if pos == self.start_position {
+                return tiles;
             }
+
+            tiles.push(pos);
+            next_pos_and_dir = self.next_position(pos, next_dir);
         }
 
-        if pos_and_dir.0 .0 < 0 || pos_and_dir.0 .0 >= self.map.len() as i32 {
-            return ((0, 0), 's');
+        Vec::new()
+    }
+
+    // Scanline parity: walking along a row, every loop tile with a northern connection
+    // (`|`, `L` or `J`) switches between outside and inside.
+    pub fn solve2(&self) -> u64 {
+        let tiles = self.loop_tiles();
+        let mut on_loop = Grid::filled(self.map.width(), self.map.height(), false);
+        for &pos in &tiles {
+            on_loop[pos] = true;
         }
 
-        if pos_and_dir.0 .1 < 0 || pos_and_dir.0 .1 >= self.map[0].len() as i32 {
-            return ((0, 0), 's');
+        let mut total: u64 = Default::default();
+        for (y, row) in self.map.rows().enumerate() {
+            let mut inside = false;
+            for (x, &tile) in row.iter().enumerate() {
+                let pos = Pos::new(y, x);
+                if !on_loop[pos] {
+                    total += inside as u64;
+                    continue;
+                }
+
+                let pipe = if tile == 'S' { self.start_shape } else { tile };
+                if matches!(pipe, '|' | 'L' | 'J') {
+                    inside = !inside;
+                }
+            }
         }
 
-        (
-            (pos_and_dir.0 .0 as usize, pos_and_dir.0 .1 as usize),
-            pos_and_dir.1,
-        )
+        total
+    }
+
+    // Shoelace formula for the area enclosed by the loop, then Pick's theorem
+    // (area = interior + boundary / 2 - 1) for the number of tiles inside.
+    pub fn solve2_shoelace(&self) -> u64 {
+        let tiles = self.loop_tiles();
+        let twice_area: i64 = tiles
+            .iter()
+            .zip(tiles.iter().cycle().skip(1))
+            .map(|(a, b)| a.col as i64 * b.row as i64 - b.col as i64 * a.row as i64)
+            .sum();
+
+        (twice_area.abs() / 2 - tiles.len() as i64 / 2 + 1) as u64
+    }
+}
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
+    }
+
+    fn variants(&self) -> Vec<Variant<'_>> {
+        vec![Variant {
+            part: 2,
+            name: "shoelace",
+            run: Box::new(|| Ok(self.solve2_shoelace().into())),
+        }]
     }
 }
@@ -1,6 +1,8 @@
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use crate::solution::{Answer, Failure, Solution};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use std::str::FromStr;
 
 pub struct Day {
     games: Vec<Vec<HashMap<String, u64>>>,
@@ -8,15 +10,24 @@ pub struct Day {
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).expect("should be able to open the file");
-        let reader = BufReader::new(file);
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
 
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
         let mut games_vec = Vec::new();
-        for line_res in reader.lines() {
-            let line = line_res.expect("should be able to read the string");
-            let order_and_games = line.split(":").filter(|&x| !x.is_empty()).collect::<Vec<&str>>();
-            let games = order_and_games[1].split(";").filter(|&x| !x.is_empty()).collect::<Vec<&str>>();
+        for (index, text) in read_lines(reader)?.iter().enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            let line = Line { number: index + 1, text };
+
+            let order_and_games = text.split(":").filter(|&x| !x.is_empty()).collect::<Vec<&str>>();
+            let games = line
+                .field(&order_and_games, 1, "`:` followed by the revealed cubes")?
+                .split(";")
+                .filter(|&x| !x.is_empty())
+                .collect::<Vec<&str>>();
 
             let mut game_vec = Vec::new();
             for game in games {
@@ -26,22 +37,26 @@ impl Day {
                 for cube in cubes {
                     let items = cube.split(" ").filter(|&x| !x.is_empty() && x != " ").collect::<Vec<&str>>();
 
-                    cube_map.insert(items[1].to_owned(), items[0].parse::<u64>().expect("expect number string"));
+                    let count = line.number::<u64>(line.field(&items, 0, "cube count")?)?;
+                    let color = line.field(&items, 1, "cube color")?;
+                    if !["red", "green", "blue"].contains(&color) {
+                        return Err(line.error(color, "`red`, `green` or `blue`"));
+                    }
+                    cube_map.insert(color.to_owned(), count);
                 }
                 game_vec.push(cube_map);
             }
             games_vec.push(game_vec);
         }
 
-
-        Self {
+        Ok(Self {
             games: games_vec,
             game_threshold: HashMap::from([
                 ("red".into(), 12),
                 ("green".into(), 13),
                 ("blue".into(), 14),
             ]),
-        }
+        })
     }
 
     pub fn solve(&self) -> u64 {
@@ -51,7 +66,7 @@ impl Day {
             let mut invalid_game: bool = false;
             for turn in game {
                 for (color, threshold) in &self.game_threshold {
-                    if turn.contains_key(color) && turn.get(color).expect("expect get non empty map value") > &threshold {
+                    if turn.contains_key(color) && turn.get(color).expect("expect get non empty map value") > threshold {
                         invalid_game = true;
                         break;
                     }
@@ -86,7 +101,7 @@ impl Day {
                 }
             }
             let mut sub_total: u64 = 1;
-            for (color, number) in color_max {
+            for (_color, number) in color_max {
                 sub_total *= number;
             }
             total += sub_total;
@@ -95,3 +110,25 @@ impl Day {
         total
     }
 }
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
+    }
+}
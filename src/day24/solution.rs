@@ -1,190 +1,437 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::math::Rational;
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use crate::solution::{Answer, Effect, Failure, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Point {
-    x: f64,
-    y: f64,
-    z: f64,
+    pub x: Rational,
+    pub y: Rational,
+    pub z: Rational,
 }
 
+impl Point {
+    pub fn axis(&self, axis: usize) -> Rational {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+
+    fn set_axis(&mut self, axis: usize, value: Rational) {
+        match axis {
+            0 => self.x = value,
+            1 => self.y = value,
+            _ => self.z = value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Vector {
-    pos: Point,
-    vel: Point,
+    pub pos: Point,
+    pub vel: Point,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intersection {
+    Parallel,
+    // Both paths lie on the same line, so they share every point of it.
+    Coincident,
+    Intersects(Point),
+    // Only in 3D: the paths are not parallel, but never meet.
+    Skew,
+}
+
+// The coordinates that take part in the crossing test; the others are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plane {
+    XY,
+    XZ,
+    YZ,
+    XYZ,
+}
+
+impl Plane {
+    pub fn axes(&self) -> &'static [usize] {
+        match self {
+            Plane::XY => &[0, 1],
+            Plane::XZ => &[0, 2],
+            Plane::YZ => &[1, 2],
+            Plane::XYZ => &[0, 1, 2],
+        }
+    }
+}
+
+impl FromStr for Plane {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "xy" => Ok(Plane::XY),
+            "xz" => Ok(Plane::XZ),
+            "yz" => Ok(Plane::YZ),
+            "xyz" | "3d" => Ok(Plane::XYZ),
+            _ => Err(format!("unknown plane `{}`, expected xy, xz, yz or xyz", value)),
+        }
+    }
 }
 
 pub struct Day {
     rows: Vec<Vector>,
-    min_coord: f64,
-    max_coord: f64,
+    min_coord: Rational,
+    max_coord: Rational,
+    plane: Plane,
+    // The rock's x and y velocity is searched up to this speed in both.
+    max_rock_speed: i128,
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).expect("should be able to open the file");
-        let reader = BufReader::new(file);
-
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
 
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
         let mut vectors: Vec<Vector> = Default::default();
-        for line_res in reader.lines() {
-            let line = line_res.expect("should be able to read the string");
-
-            let line_part = line.split("@").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
-            let line_pos_part = line_part[0].split(",").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
-            let line_vel_part = line_part[1].split(",").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
+        for (index, text) in read_lines(reader)?.iter().enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            let line = Line { number: index + 1, text };
+
+            let line_part = text.split("@").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
+            let line_pos_part = line.field(&line_part, 0, "a position")?.split(",").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
+            let line_vel_part = line
+                .field(&line_part, 1, "`@` followed by a velocity")?
+                .split(",")
+                .filter(|x| !x.is_empty())
+                .collect::<Vec<&str>>();
+            let coordinate = |parts: &[&str], index: usize, expected: &str| -> Result<Rational, ParseError> {
+                Ok(Rational::from(line.number::<i128>(line.field(parts, index, expected)?)?))
+            };
 
-            let rows: Vec<Vector> = Default::default();
             let vector = Vector {
                 pos: Point {
-                    x: line_pos_part[0].trim().parse::<f64>().expect("should be number"),
-                    y: line_pos_part[1].trim().parse::<f64>().expect("should be number"),
-                    z: line_pos_part[2].trim().parse::<f64>().expect("should be number"),
+                    x: coordinate(&line_pos_part, 0, "x position")?,
+                    y: coordinate(&line_pos_part, 1, "y position")?,
+                    z: coordinate(&line_pos_part, 2, "z position")?,
                 },
                 vel: Point {
-                    x: line_vel_part[0].trim().parse::<f64>().expect("should be number"),
-                    y: line_vel_part[1].trim().parse::<f64>().expect("should be number"),
-                    z: line_vel_part[2].trim().parse::<f64>().expect("should be number"),
+                    x: coordinate(&line_vel_part, 0, "x velocity")?,
+                    y: coordinate(&line_vel_part, 1, "y velocity")?,
+                    z: coordinate(&line_vel_part, 2, "z velocity")?,
                 },
             };
             vectors.push(vector)
         }
 
-        Self {
+        Ok(Self {
             rows: vectors,
-            // min_coord: 7f64,
-            // max_coord: 27f64,
-            min_coord: 200000000000000f64,
-            max_coord: 400000000000000f64,
-        }
+            min_coord: Rational::from(200000000000000i128),
+            max_coord: Rational::from(400000000000000i128),
+            plane: Plane::XY,
+            max_rock_speed: DEFAULT_MAX_ROCK_SPEED,
+        })
     }
 
-    fn get_determinant(&self, a: f64, b: f64, c: f64, d: f64) -> f64 {
-        return (a * d) - (b * c);
+    // The test area is the same inclusive range on every axis of the plane; the example uses 7..=27.
+    pub fn with_area(mut self, min_coord: i128, max_coord: i128) -> Self {
+        self.min_coord = Rational::from(min_coord);
+        self.max_coord = Rational::from(max_coord);
+        self
     }
 
-    fn get_intersect_point(&self, vec1: &Vector, vec2: &Vector) -> Point {
-        let div = self.get_determinant(-1f64 * vec1.vel.x, -1f64 * vec2.vel.x, -1f64 * vec1.vel.y, -1f64 * vec2.vel.y);
-        if div == 0f64 {
-            return Point {
-                x: 0f64,
-                y: 0f64,
-                z: 0f64,
-            };
-        }
-
-        let c1 = self.get_determinant(vec1.pos.x, vec1.pos.y, vec1.pos.x + vec1.vel.x, vec1.pos.y + vec1.vel.y);
-        let c2 = self.get_determinant(vec2.pos.x, vec2.pos.y, vec2.pos.x + vec2.vel.x, vec2.pos.y + vec2.vel.y);
-
-        let x_div = self.get_determinant(c1, c2, -1f64 * vec1.vel.x, -1f64 * vec2.vel.x);
-        let y_div = self.get_determinant(c1, c2, -1f64 * vec1.vel.y, -1f64 * vec2.vel.y);
+    pub fn with_plane(mut self, plane: Plane) -> Self {
+        self.plane = plane;
+        self
+    }
 
-        println!("intersect {} {} {} {}", c1, c2, x_div, y_div);
+    pub fn with_max_rock_speed(mut self, max_rock_speed: i128) -> Self {
+        self.max_rock_speed = max_rock_speed;
+        self
+    }
 
-        Point {
-            x: x_div / div,
-            y: y_div / div,
-            z: 0f64,
-        }
+    fn get_determinant(&self, a: Rational, b: Rational, c: Rational, d: Rational) -> Rational {
+        (a * d) - (b * c)
     }
 
-    fn get_intersect_point2(&self, vec1: &Vector, vec2: &Vector) -> Point {
-        let div = self.get_determinant(vec1.vel.x, vec2.vel.x, vec1.vel.y, vec2.vel.y);
-        if div == 0f64 {
-            return Point {
-                x: 0f64,
-                y: 0f64,
-                z: 0f64,
-            };
+    // Where the paths of two hailstones cross in the plane of the axes `a` and `b`, ignoring when
+    // they get there. The other coordinate of the point is left at zero.
+    fn intersect_axes(&self, vec1: &Vector, vec2: &Vector, a: usize, b: usize) -> Intersection {
+        let (pos1, vel1) = ([vec1.pos.axis(a), vec1.pos.axis(b)], [vec1.vel.axis(a), vec1.vel.axis(b)]);
+        let (pos2, vel2) = ([vec2.pos.axis(a), vec2.pos.axis(b)], [vec2.vel.axis(a), vec2.vel.axis(b)]);
+
+        let div = self.get_determinant(vel1[0], vel2[0], vel1[1], vel2[1]);
+        if div == Rational::ZERO {
+            let offset = [pos2[0] - pos1[0], pos2[1] - pos1[1]];
+            if self.get_determinant(offset[0], vel1[0], offset[1], vel1[1]) == Rational::ZERO {
+                return Intersection::Coincident;
+            }
+            return Intersection::Parallel;
         }
 
-        let c1 = self.get_determinant(vec1.pos.x, vec1.pos.y, vec1.pos.x + vec1.vel.x, vec1.pos.y + vec1.vel.y);
-        let c2 = self.get_determinant(vec2.pos.x, vec2.pos.y, vec2.pos.x + vec2.vel.x, vec2.pos.y + vec2.vel.y);
-
-        let x_div = self.get_determinant(c1, c2, vec1.vel.y, vec2.vel.y);
-        let y_div = self.get_determinant(vec1.vel.x, vec2.vel.x, c1, c2);
-
-        println!("intersect {} {} {} {}", c1, c2, x_div, y_div);
+        let c1 = self.get_determinant(pos1[0], pos1[1], pos1[0] + vel1[0], pos1[1] + vel1[1]);
+        let c2 = self.get_determinant(pos2[0], pos2[1], pos2[0] + vel2[0], pos2[1] + vel2[1]);
+
+        let mut point = Point {
+            x: Rational::ZERO,
+            y: Rational::ZERO,
+            z: Rational::ZERO,
+        };
+        point.set_axis(a, self.get_determinant(vel1[0], vel2[0], c1, c2) / div);
+        point.set_axis(b, self.get_determinant(vel1[1], vel2[1], c1, c2) / div);
+        Intersection::Intersects(point)
+    }
 
+    // The point `vector` reaches when its coordinate on `axis` equals the one of `point`.
+    fn point_along(&self, vector: &Vector, point: &Point, axes: [usize; 2]) -> Point {
+        let axis = if vector.vel.axis(axes[0]) != Rational::ZERO { axes[0] } else { axes[1] };
+        let t = (point.axis(axis) - vector.pos.axis(axis)) / vector.vel.axis(axis);
         Point {
-            x: x_div / div,
-            y: y_div / div,
-            z: 0f64,
+            x: vector.pos.x + vector.vel.x * t,
+            y: vector.pos.y + vector.vel.y * t,
+            z: vector.pos.z + vector.vel.z * t,
         }
     }
 
-    fn get_intersect_point3(&self, vec1: &Vector, vec2: &Vector) -> Point {
-        let div = self.get_determinant(vec1.vel.x, vec2.vel.x, vec1.vel.y, vec2.vel.y);
-        if div == 0f64 {
-            return Point {
-                x: 0f64,
-                y: 0f64,
-                z: 0f64,
-            };
-        }
-
-        let c1 = vec1.pos.y + ((vec1.pos.x.abs() / vec1.vel.x.abs()) * vec1.vel.y);
-        let c2 = vec2.pos.y + ((vec2.pos.x.abs() / vec2.vel.x.abs()) * vec2.vel.y);
-
-        let x_div = self.get_determinant(c1, c2, vec1.vel.y, vec2.vel.y);
-        let y_div = self.get_determinant(vec1.vel.x, vec2.vel.x, c1, c2);
-
-        println!("intersect {} {} {} {}", c1, c2, x_div, y_div);
+    pub fn intersect(&self, vec1: &Vector, vec2: &Vector) -> Intersection {
+        match self.plane {
+            Plane::XY => self.intersect_axes(vec1, vec2, 0, 1),
+            Plane::XZ => self.intersect_axes(vec1, vec2, 0, 2),
+            Plane::YZ => self.intersect_axes(vec1, vec2, 1, 2),
+            Plane::XYZ => {
+                // Cross in any projection where the paths are not parallel, then check the
+                // points both hailstones reach there are the same.
+                let mut coincident = true;
+                for [a, b] in [[0, 1], [0, 2], [1, 2]] {
+                    match self.intersect_axes(vec1, vec2, a, b) {
+                        Intersection::Intersects(point) => {
+                            let point1 = self.point_along(vec1, &point, [a, b]);
+                            let point2 = self.point_along(vec2, &point, [a, b]);
+                            if point1 == point2 {
+                                return Intersection::Intersects(point1);
+                            }
+                            return Intersection::Skew;
+                        }
+                        Intersection::Parallel => coincident = false,
+                        _ => {}
+                    }
+                }
 
-        Point {
-            x: x_div / div,
-            y: y_div / div,
-            z: 0f64,
+                if coincident {
+                    Intersection::Coincident
+                } else {
+                    Intersection::Parallel
+                }
+            }
         }
     }
 
-    fn is_sign(&self, number: f64) -> i32 {
-        if number < 0f64 {
-            return -1;
-        } else if number > 0f64 {
-            return 1;
-        }
-        return 0;
+    fn is_future_crossed(&self, intersect: &Point, point: &Point, point_vel: &Point) -> bool {
+        self.plane
+            .axes()
+            .iter()
+            .all(|&axis| (intersect.axis(axis) - point.axis(axis)).signum() == point_vel.axis(axis).signum())
     }
 
-    fn is_future_crossed(&self, intersect: &Point, point: &Point, point_vel: &Point) -> bool {
-        self.is_sign(intersect.x - point.x) == self.is_sign(point_vel.x) &&
-            self.is_sign(intersect.y - point.y) == self.is_sign(point_vel.y)
+    fn is_inside(&self, intersect: &Point) -> bool {
+        self.plane
+            .axes()
+            .iter()
+            .all(|&axis| self.min_coord <= intersect.axis(axis) && intersect.axis(axis) <= self.max_coord)
     }
 
     pub fn solve(&self) -> u64 {
         let mut total: u64 = Default::default();
 
         for (index, row) in self.rows.iter().enumerate() {
-            for t_row in self.rows[index..].iter() {
-                let intersect: Point = self.get_intersect_point2(row, t_row);
-                if intersect.x == 0f64 && intersect.y == 0f64 {
+            for t_row in self.rows[index + 1..].iter() {
+                let Intersection::Intersects(intersect) = self.intersect(row, t_row) else {
                     continue;
-                }
+                };
 
                 if !self.is_future_crossed(&intersect, &row.pos, &row.vel) ||
                     !self.is_future_crossed(&intersect, &t_row.pos, &t_row.vel) {
                     continue;
                 }
 
-                if !(self.min_coord <= intersect.x) || !(self.max_coord >= intersect.x) ||
-                    !(self.min_coord <= intersect.y) || !(self.max_coord >= intersect.y) {
+                if !self.is_inside(&intersect) {
                     continue;
                 }
 
                 total += 1;
-
-                // if self.is_future_crossed(&intersect, &row.pos, &row.vel) &&
-                //     self.is_future_crossed(&intersect, &t_row.pos, &t_row.vel) {
-                //     if (self.min_coord <= intersect.x) && (self.max_coord >= intersect.x) &&
-                //         (self.min_coord <= intersect.y) && (self.max_coord >= intersect.y) {
-                //         total += 1;
-                //     }
-                // }
             }
         }
 
         total
-        // 13135 is too low
-        // 26270 is too high
-        // how to actually get C value?
+    }
+
+    fn hailstones(&self) -> Vec<([i128; 3], [i128; 3])> {
+        let integer = |value: Rational| value.to_integer().expect("hailstones are parsed from integers");
+        self.rows
+            .iter()
+            .map(|row| {
+                (
+                    [integer(row.pos.x), integer(row.pos.y), integer(row.pos.z)],
+                    [integer(row.vel.x), integer(row.vel.y), integer(row.vel.z)],
+                )
+            })
+            .collect()
+    }
+
+    // Seen from the rock, every hailstone moves with its velocity minus the rock's and passes
+    // through the rock's starting point. Candidate x/y velocities are tried from slow to fast.
+    pub fn find_rock(&self) -> Option<([i128; 3], [i128; 3])> {
+        let hailstones = self.hailstones();
+        if hailstones.len() < 2 {
+            return None;
+        }
+
+        for speed in 0..=self.max_rock_speed {
+            for vx in -speed..=speed {
+                let vys: Vec<i128> = if vx.abs() == speed {
+                    (-speed..=speed).collect()
+                } else {
+                    vec![-speed, speed]
+                };
+                for vy in vys {
+                    if let Some(rock) = rock_with_velocity(&hailstones, vx, vy) {
+                        return Some(rock);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn solve2(&self) -> Result<i64, String> {
+        let (position, _) = self.find_rock().ok_or(format!(
+            "no rock with x and y speeds from -{0} to {0} hits every hailstone (see --opt max-speed=N)",
+            self.max_rock_speed
+        ))?;
+        Ok(position.iter().sum::<i128>() as i64)
+    }
+}
+
+pub const DEFAULT_MAX_ROCK_SPEED: i128 = 1000;
+
+fn cross(a: [i128; 2], b: [i128; 2]) -> i128 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+fn dot(a: [i128; 2], b: [i128; 2]) -> i128 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn rock_with_velocity(hailstones: &[([i128; 3], [i128; 3])], vx: i128, vy: i128) -> Option<([i128; 3], [i128; 3])> {
+    let relative = |(pos, vel): &([i128; 3], [i128; 3])| ([pos[0], pos[1]], [vel[0] - vx, vel[1] - vy]);
+
+    // Where the first hailstone meets the first one that is not parallel to it.
+    let (p0, d0) = relative(&hailstones[0]);
+    let start = if d0 == [0, 0] {
+        p0
+    } else {
+        let (p1, d1) = hailstones[1..].iter().map(relative).find(|&(_, d1)| cross(d0, d1) != 0)?;
+        let numerator = cross([p1[0] - p0[0], p1[1] - p0[1]], d1);
+        let denominator = cross(d0, d1);
+        if numerator % denominator != 0 || numerator / denominator < 0 {
+            return None;
+        }
+        let t = numerator / denominator;
+        [p0[0] + t * d0[0], p0[1] + t * d0[1]]
+    };
+
+    // The time of every collision, or `None` for a hailstone that stands still next to the rock.
+    let mut times: Vec<Option<i128>> = Vec::with_capacity(hailstones.len());
+    for hailstone in hailstones {
+        let (p, d) = relative(hailstone);
+        let offset = [start[0] - p[0], start[1] - p[1]];
+        if d == [0, 0] {
+            if offset != [0, 0] {
+                return None;
+            }
+            times.push(None);
+            continue;
+        }
+
+        let numerator = dot(offset, d);
+        if cross(offset, d) != 0 || numerator < 0 || numerator % dot(d, d) != 0 {
+            return None;
+        }
+        times.push(Some(numerator / dot(d, d)));
+    }
+
+    // Two collisions at different times fix the rock's z velocity and position.
+    let known: Vec<(usize, i128)> = times.iter().enumerate().filter_map(|(i, t)| t.map(|t| (i, t))).collect();
+    let &(i, ti) = known.first()?;
+    let &(j, tj) = known.iter().find(|&&(_, t)| t != ti)?;
+    let z_at = |index: usize, t: i128| hailstones[index].0[2] + hailstones[index].1[2] * t;
+    let numerator = z_at(i, ti) - z_at(j, tj);
+    if numerator % (ti - tj) != 0 {
+        return None;
+    }
+    let vz = numerator / (ti - tj);
+    let z = z_at(i, ti) - vz * ti;
+
+    for (index, t) in times.iter().enumerate() {
+        let (pos, vel) = hailstones[index];
+        let hit = match t {
+            Some(t) => z + vz * t == z_at(index, *t),
+            None if vz == vel[2] => z == pos[2],
+            None => (pos[2] - z) % (vz - vel[2]) == 0 && (pos[2] - z) / (vz - vel[2]) >= 0,
+        };
+        if !hit {
+            return None;
+        }
+    }
+
+    Some(([start[0], start[1], z], [vx, vy, vz]))
+}
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(self.solve2().map(Answer::from).map_err(Failure::Failed))
+    }
+
+    fn configure(&mut self, key: &str, value: &str) -> Result<Effect, String> {
+        match key {
+            "area" => {
+                let range = value
+                    .split_once("..")
+                    .and_then(|(min, max)| Some((min.trim().parse::<i128>().ok()?, max.trim().parse::<i128>().ok()?)))
+                    .filter(|(min, max)| min <= max);
+                let (min, max) = range.ok_or(format!("`{}` is not an area like 7..27", value))?;
+                self.min_coord = Rational::from(min);
+                self.max_coord = Rational::from(max);
+            }
+            "plane" => self.plane = value.parse::<Plane>()?,
+            "max-speed" => {
+                let speed = value.parse::<i128>().ok().filter(|&speed| speed >= 0);
+                self.max_rock_speed = speed.ok_or(format!("`{}` is not a speed", value))?;
+                // A wider search finds the same rock, if any.
+                return Ok(Effect::KeepsAnswers);
+            }
+            _ => return Err(format!("unknown option `{}`, expected area, plane or max-speed", key)),
+        }
+        Ok(Effect::ChangesAnswers)
     }
 }
@@ -1,99 +1,69 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::grid::{Grid, Pos};
+use crate::parse::{digit_value, read_file, Line, ParseError};
+use crate::solution::{Answer, Effect, Failure, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
 
 pub struct Day {
-    parts: Vec<Vec<String>>,
-    max_y: i32,
-    max_x: i32,
+    parts: Grid<char>,
+    // Whether `０` to `９` are digits rather than symbols.
+    full_width: bool,
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).expect("should be able to open the file");
-        let reader = BufReader::new(file);
-
-        let mut parts = Vec::new();
-        for line_res in reader.lines() {
-            let line = line_res.expect("should be able to read the string");
-            let mut row = Vec::new();
-            for item in line.split("").filter(|&x| !x.is_empty()).collect::<Vec<&str>>() {
-                row.push(item.to_string());
-            }
-            parts.push(row);
-        }
-
-        let max_y = parts.len() as i32;
-        let max_x = parts[0].len() as i32;
-
-        return Self {
-            parts,
-            max_y,
-            max_x,
-        };
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
     }
 
-    fn check_symbol(&self, y: i32, x: i32) -> bool {
-        if y < 0 || y + 1 > self.max_y || x < 0 || x + 1 > self.max_x {
-            return false;
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        let parts = Grid::from_reader(reader, |_, item| Ok(item.chars().next().unwrap_or_default()))?;
+
+        if parts.is_empty() {
+            let line = Line { number: 1, text: "" };
+            return Err(line.error("", "an engine schematic"));
         }
 
-        let y_index = y as usize;
-        let x_index = x as usize;
+        Ok(Self { parts, full_width: false })
+    }
 
-        let item = self.parts[y_index][x_index].to_string();
+    pub fn set_full_width(&mut self, full_width: bool) {
+        self.full_width = full_width;
+    }
 
-        return item != "0" &&
-            item != "1" &&
-            item != "2" &&
-            item != "3" &&
-            item != "4" &&
-            item != "5" &&
-            item != "6" &&
-            item != "7" &&
-            item != "8" &&
-            item != "9" &&
-            item != ".";
+    fn digit(&self, item: char) -> Option<u8> {
+        digit_value(item, self.full_width)
     }
 
-    fn check_number(&self, y: i32, x: i32) -> bool {
-        if y < 0 || y + 1 > self.max_y || x < 0 || x + 1 > self.max_x {
-            return false;
+    fn check_symbol(&self, pos: Option<Pos>) -> bool {
+        match pos.and_then(|pos| self.parts.get(pos)) {
+            Some(item) => self.digit(*item).is_none() && *item != '.',
+            None => false,
         }
+    }
 
-        let y_index = y as usize;
-        let x_index = x as usize;
-
-        let item = self.parts[y_index][x_index].to_string();
+    fn check_number(&self, pos: Option<Pos>) -> bool {
+        pos.and_then(|pos| self.parts.get(pos)).is_some_and(|item| self.digit(*item).is_some())
+    }
 
-        return item == "0" ||
-            item == "1" ||
-            item == "2" ||
-            item == "3" ||
-            item == "4" ||
-            item == "5" ||
-            item == "6" ||
-            item == "7" ||
-            item == "8" ||
-            item == "9";
+    fn number(&self, digits: &[char]) -> u64 {
+        digits.iter().fold(0, |number, item| number * 10 + self.digit(*item).expect("only digits") as u64)
     }
 
     pub fn solve(&self) -> u64 {
         let mut total: u64 = Default::default();
 
-        for (y_index, row) in self.parts.iter().enumerate() {
-            let mut number: String = Default::default();
+        for (y, row) in self.parts.rows().enumerate() {
+            let mut number: Vec<char> = Default::default();
             let mut is_valid = false;
-            for (x_index, item) in row.iter().enumerate() {
-                let y = y_index as i32;
-                let x = x_index as i32;
+            for (x, item) in row.iter().enumerate() {
+                let pos = Pos::new(y, x);
 
-                if self.check_number(y, x) {
-                    number += item;
+                if self.digit(*item).is_some() {
+                    number.push(*item);
                 } else {
                     if !number.is_empty() {
                         if is_valid {
-                            let number_int: u64 = number.parse().expect("string should contain only number");
-                            total += number_int;
+                            total += self.number(&number);
 
                             is_valid = Default::default();
                         }
@@ -105,150 +75,121 @@ impl Day {
                 }
 
                 if !is_valid {
-                    is_valid = self.check_symbol(y - 1, x) ||
-                        self.check_symbol(y - 1, x - 1) ||
-                        self.check_symbol(y - 1, x + 1) ||
-                        self.check_symbol(y, x - 1) ||
-                        self.check_symbol(y, x + 1) ||
-                        self.check_symbol(y + 1, x) ||
-                        self.check_symbol(y + 1, x - 1) ||
-                        self.check_symbol(y + 1, x + 1);
+                    is_valid = self.parts.neighbours8(pos).any(|neighbour| self.check_symbol(Some(neighbour)));
                 }
             }
 
             if !number.is_empty() && is_valid {
-                let number_int: u64 = number.parse().expect("string should contain only number");
-                total += number_int;
+                total += self.number(&number);
             }
         }
 
         total
     }
 
-    fn check_gear(&self, item: &String) -> bool {
-        return item == "*";
+    fn check_gear(&self, item: &char) -> bool {
+        *item == '*'
     }
 
-    fn get_north_gear_ratio(&self, y: i32, x: i32) -> (u64, u32) {
+    // Above or below a gear, a number touching the middle column covers the whole row of three.
+    fn get_vertical_gear_ratio(&self, pos: Pos, d_row: isize) -> (u64, u32) {
         let mut number: u64 = 1;
         let mut count: u32 = 0;
-        if self.check_number(y - 1, x) {
-            number = self.get_gear_ratio(y - 1, x);
+        let middle = self.parts.offset(pos, d_row, 0);
+        if self.check_number(middle) {
+            number = self.get_gear_ratio(middle.unwrap());
             count += 1;
         } else {
-            if self.check_number(y - 1, x - 1) {
-                number *= self.get_gear_ratio(y - 1, x - 1);
-                count += 1;
-            }
-            if self.check_number(y - 1, x + 1) {
-                number *= self.get_gear_ratio(y - 1, x + 1);
-                count += 1;
+            for d_col in [-1, 1] {
+                let side = self.parts.offset(pos, d_row, d_col);
+                if self.check_number(side) {
+                    number *= self.get_gear_ratio(side.unwrap());
+                    count += 1;
+                }
             }
         }
 
-        return (number, count);
+        (number, count)
     }
 
-    fn get_south_gear_ratio(&self, y: i32, x: i32) -> (u64, u32) {
-        let mut number: u64 = 1;
-        let mut count: u32 = 0;
-        if self.check_number(y + 1, x) {
-            number = self.get_gear_ratio(y + 1, x);
-            count += 1;
+    fn get_horizontal_gear_ratio(&self, pos: Pos, d_col: isize) -> (u64, u32) {
+        let side = self.parts.offset(pos, 0, d_col);
+        if self.check_number(side) {
+            (self.get_gear_ratio(side.unwrap()), 1)
         } else {
-            if self.check_number(y + 1, x - 1) {
-                number *= self.get_gear_ratio(y + 1, x - 1);
-                count += 1;
-            }
-            if self.check_number(y + 1, x + 1) {
-                number *= self.get_gear_ratio(y + 1, x + 1);
-                count += 1;
-            }
+            (1, 0)
         }
-
-        return (number, count);
     }
 
-    fn get_west_gear_ratio(&self, y: i32, x: i32) -> (u64, u32) {
-        let mut number: u64 = 1;
-        let mut count: u32 = 0;
-        if self.check_number(y, x - 1) {
-            number = self.get_gear_ratio(y, x - 1);
-            count += 1;
-        }
+    fn get_gear_ratio(&self, pos: Pos) -> u64 {
+        let row = self.parts.row(pos.row);
+        let start = row[..pos.col].iter().rposition(|x| self.digit(*x).is_none()).map_or(0, |x| x + 1);
+        let end = row[pos.col..].iter().position(|x| self.digit(*x).is_none()).map_or(row.len(), |x| pos.col + x);
 
-        return (number, count);
+        self.number(&row[start..end])
     }
 
-    fn get_east_gear_ratio(&self, y: i32, x: i32) -> (u64, u32) {
-        let mut number: u64 = 1;
-        let mut count: u32 = 0;
-        if self.check_number(y, x + 1) {
-            number = self.get_gear_ratio(y, x + 1);
-            count += 1;
-        }
+    pub fn solve2(&self) -> u64 {
+        let mut total: u64 = Default::default();
 
-        return (number, count);
-    }
+        for (pos, item) in self.parts.iter() {
+            if !self.check_gear(item) {
+                continue;
+            }
 
-    fn get_gear_ratio(&self, y: i32, x: i32) -> u64 {
-        let mut number: String = Default::default();
-        let mut x_left = x;
-        while self.check_number(y, x_left) {
-            let item = &self.parts[y as usize][x_left as usize];
-            number = item.to_string() + &*number;
-            x_left -= 1;
-        }
-        x_left = x + 1;
-        while self.check_number(y, x_left) {
-            let item = &self.parts[y as usize][x_left as usize];
-            number = number + &*item.to_string();
-            x_left += 1;
+            let mut number: u64 = 1;
+            let mut count: u32 = Default::default();
+            let ratios = [
+                self.get_vertical_gear_ratio(pos, -1),
+                self.get_vertical_gear_ratio(pos, 1),
+                self.get_horizontal_gear_ratio(pos, -1),
+                self.get_horizontal_gear_ratio(pos, 1),
+            ];
+            for (res, count_res) in ratios {
+                if count_res > 0 {
+                    count += count_res;
+                    number *= res;
+                }
+            }
+
+            if count == 2 {
+                total += number;
+            }
         }
 
-        return number.parse().expect("string should contain only number");
+        total
     }
+}
 
-    pub fn solve2(&self) -> u64 {
-        let mut total: u64 = Default::default();
+impl FromStr for Day {
+    type Err = ParseError;
 
-        for (y_index, row) in self.parts.iter().enumerate() {
-            for (x_index, item) in row.iter().enumerate() {
-                let mut number: u64 = 1;
-                let y = y_index as i32;
-                let x = x_index as i32;
-                let mut count: u32 = Default::default();
-                if self.check_gear(item) {
-                    let (res, count_res) = self.get_north_gear_ratio(y, x);
-                    if count_res > 0 {
-                        count += count_res;
-                        number *= res;
-                    }
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
 
-                    let (res, count_res) = self.get_south_gear_ratio(y, x);
-                    if count_res > 0 {
-                        count += count_res;
-                        number *= res;
-                    }
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
 
-                    let (res, count_res) = self.get_west_gear_ratio(y, x);
-                    if count_res > 0 {
-                        count += count_res;
-                        number *= res;
-                    }
-                    let (res, count_res) = self.get_east_gear_ratio(y, x);
-                    if count_res > 0 {
-                        count += count_res;
-                        number *= res;
-                    }
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
 
-                    if count == 2 {
-                        total += number;
-                    }
-                }
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
+    }
+
+    // `full-width=true` reads `０` to `９` as digits.
+    fn configure(&mut self, key: &str, value: &str) -> Result<Effect, String> {
+        match key {
+            "full-width" => {
+                self.set_full_width(value.parse().map_err(|_| format!("`{}` is not true or false", value))?);
+                Ok(Effect::ChangesAnswers)
             }
+            _ => Err(format!("unknown option `{}`, expected full-width", key)),
         }
-
-        total
     }
 }
@@ -1,7 +1,8 @@
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use crate::solution::{Answer, Failure, Solution};
 use std::collections::HashMap;
-use std::fs::File;
-use std::hash::Hash;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use std::str::FromStr;
 
 pub struct Day {
     winning_numbers: Vec<HashMap<i32, bool>>,
@@ -9,43 +10,53 @@ pub struct Day {
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).expect("should be able to open the file");
-        let reader = BufReader::new(file);
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
 
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
         let mut winning_numbers = Vec::new();
         let mut numbers = Vec::new();
-        for line_res in reader.lines() {
-            let line = line_res.expect("should be able to read the string");
-
-            let cards = line.split(":").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
-            let number_str = cards[1].split("|").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
-            let winning_number_str = number_str[0].split(" ").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
-            let your_number_str = number_str[1].split(" ").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
+        for (index, text) in read_lines(reader)?.iter().enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            let line = Line { number: index + 1, text };
+
+            let cards = text.split(":").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
+            let number_str = line
+                .field(&cards, 1, "`:` followed by the card numbers")?
+                .split("|")
+                .filter(|x| !x.is_empty())
+                .collect::<Vec<&str>>();
+            let winning_number_str = line
+                .field(&number_str, 0, "winning numbers")?
+                .split(" ")
+                .filter(|x| !x.is_empty())
+                .collect::<Vec<&str>>();
+            let your_number_str = line
+                .field(&number_str, 1, "`|` followed by your numbers")?
+                .split(" ")
+                .filter(|x| !x.is_empty())
+                .collect::<Vec<&str>>();
 
             let mut winning_number: HashMap<i32, bool> = HashMap::new();
             for item in winning_number_str {
-                winning_number.insert(
-                    item.parse::<i32>().expect("should be number"),
-                    true,
-                );
+                winning_number.insert(line.number::<i32>(item)?, true);
             }
             winning_numbers.push(winning_number);
 
             let mut your_number: Vec<i32> = Vec::new();
             for item in your_number_str {
-                your_number.push(
-                    item.parse::<i32>().expect("should be number"),
-                );
+                your_number.push(line.number::<i32>(item)?);
             }
             numbers.push(your_number);
         }
 
-
-        Self {
+        Ok(Self {
             winning_numbers,
             numbers,
-        }
+        })
     }
 
     pub fn solve(&self) -> i64 {
@@ -69,10 +80,7 @@ impl Day {
     pub fn solve2(&self) -> i64 {
         let mut total: i64 = Default::default();
 
-        let mut copies: Vec<i64> = Default::default();
-        for _ in &self.numbers {
-            copies.push(1);
-        }
+        let mut copies: Vec<i64> = vec![1; self.numbers.len()];
 
         for (index, card) in self.numbers.iter().enumerate() {
             let mut n_match: i32 = -1;
@@ -93,3 +101,25 @@ impl Day {
         total
     }
 }
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
+    }
+}
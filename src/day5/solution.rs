@@ -1,294 +1,412 @@
-use std::fs::File;
-use std::hash::Hash;
-use std::io::{BufRead, BufReader};
+use crate::interval::{intersect_ranges, IntervalMap};
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use crate::solution::{Answer, Effect, Failure, Solution, Variant};
+use std::collections::{HashMap, VecDeque};
+use std::io::BufRead;
+use std::ops::Range;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Seeds a brute-force worker maps before it reports progress and checks for cancellation.
+pub const BRUTEFORCE_CHUNK: u64 = 1 << 20;
+
+// One `source-to-target map:` section of the almanac.
+pub struct Stage {
+    pub source: String,
+    pub target: String,
+    pub map: IntervalMap,
+}
+
+// The seeds behind a range of locations, and which of them the almanac actually plants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReverseLookup {
+    pub locations: Range<u64>,
+    pub seeds: Vec<Range<u64>>,
+    // Seeds listed on the `seeds:` line, read as single numbers (part 1).
+    pub listed: Vec<u64>,
+    // Seeds inside the `seeds:` ranges (part 2).
+    pub planted: Vec<Range<u64>>,
+}
 
 pub struct Day {
     seeds: Vec<u64>,
-    seed_soils: Vec<Vec<u64>>,
-    soil_fers: Vec<Vec<u64>>,
-    fer_waters: Vec<Vec<u64>>,
-    water_lights: Vec<Vec<u64>>,
-    light_temps: Vec<Vec<u64>>,
-    temp_hums: Vec<Vec<u64>>,
-    hum_locs: Vec<Vec<u64>>,
+    stages: Vec<Stage>,
+    // Indices into `stages` leading from `source` to `target`.
+    path: Vec<usize>,
+    source: String,
+    target: String,
+    seed_to_location: IntervalMap,
+    reverse: Option<Range<u64>>,
+    // Brute-force workers; all available cores when unset.
+    threads: Option<usize>,
+    // How long the brute-force variant may run before it is cancelled.
+    timeout: Option<Duration>,
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).expect("should be able to open the file");
-        let reader = BufReader::new(file);
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
 
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
         let mut seeds = Vec::new();
-        let mut seed_soils = Vec::new();
-        let mut soil_fers = Vec::new();
-        let mut fer_waters = Vec::new();
-        let mut water_lights = Vec::new();
-        let mut light_temps = Vec::new();
-        let mut temp_hums = Vec::new();
-        let mut hum_locs = Vec::new();
-        let mut step: u32 = Default::default();
-        for line_res in reader.lines() {
-            let line = line_res.expect("should be able to read the string");
-
-            if line.is_empty() {
+        let mut seen_seeds = false;
+        let mut stages: Vec<Stage> = Vec::new();
+        // The header line of every stage, to point at sections without rows.
+        let mut headers: Vec<(usize, &str)> = Vec::new();
+        let lines = read_lines(reader)?;
+        for (index, text) in lines.iter().enumerate() {
+            if text.is_empty() {
                 continue;
             }
+            let line = Line { number: index + 1, text };
 
-            if line.contains("seeds:") {
-                step = 1;
-            } else if line.contains("seed-to-soil map:") {
-                step = 2;
-                continue;
-            } else if line.contains("soil-to-fertilizer map:") {
-                step = 3;
-                continue;
-            } else if line.contains("fertilizer-to-water map:") {
-                step = 4;
-                continue;
-            } else if line.contains("water-to-light map:") {
-                step = 5;
-                continue;
-            } else if line.contains("light-to-temperature map:") {
-                step = 6;
-                continue;
-            } else if line.contains("temperature-to-humidity map:") {
-                step = 7;
-                continue;
-            } else if line.contains("humidity-to-location map:") {
-                step = 8;
+            if let Some(numbers) = text.strip_prefix("seeds:") {
+                let numbers = numbers.split(" ").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
+                let mut listed: Vec<u64> = Vec::new();
+                for number in &numbers {
+                    listed.push(line.number::<u64>(number)?);
+                }
+                // Part 2 reads the seeds as pairs of start and length.
+                if !listed.len().is_multiple_of(2) {
+                    return Err(line.error("", "a length after the last start"));
+                }
+                for (pair, tokens) in listed.chunks(2).zip(numbers.chunks(2)) {
+                    if pair[1] == 0 || pair[0].checked_add(pair[1]).is_none() {
+                        return Err(line.error(tokens[1], "a length of at least 1 that keeps the range within u64"));
+                    }
+                }
+                seeds.extend(listed);
+                seen_seeds = true;
                 continue;
             }
 
-            match step {
-                1 => {
-                    let parts = line.split(":").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
-                    let numbers = parts[1].split(" ").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
-                    for number in numbers {
-                        seeds.push(number.parse::<u64>().expect("should be number"));
-                    }
+            if let Some(header) = text.strip_suffix(" map:") {
+                let (source, target) = header
+                    .split_once("-to-")
+                    .filter(|(source, target)| !source.is_empty() && !target.is_empty())
+                    .ok_or_else(|| line.error(header, "a `source-to-target` pair of categories"))?;
+                if stages.iter().any(|stage| stage.source == source && stage.target == target) {
+                    return Err(line.error(header, "a map that is not listed twice"));
                 }
-                2 | 3 | 4 | 5 | 6 | 7 | 8 => {
-                    let numbers = line.split(" ").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
-                    let mut numbers_vec: Vec<u64> = Vec::new();
-                    for number in numbers {
-                        numbers_vec.push(number.parse::<u64>().expect("should be number"));
-                    }
+                stages.push(Stage {
+                    source: source.to_string(),
+                    target: target.to_string(),
+                    map: IntervalMap::new(),
+                });
+                headers.push((index + 1, text));
+                continue;
+            }
 
-                    match step {
-                        2 => {
-                            seed_soils.push(numbers_vec);
-                        }
-                        3 => {
-                            soil_fers.push(numbers_vec);
-                        }
-                        4 => {
-                            fer_waters.push(numbers_vec);
-                        }
-                        5 => {
-                            water_lights.push(numbers_vec);
-                        }
-                        6 => {
-                            light_temps.push(numbers_vec);
-                        }
-                        7 => {
-                            temp_hums.push(numbers_vec);
-                        }
-                        8 => {
-                            hum_locs.push(numbers_vec);
-                        }
-                        _ => {}
-                    }
-                }
-                _ => {}
+            let Some(stage) = stages.last_mut().filter(|_| seen_seeds) else {
+                return Err(line.error(text, "`seeds:` or a `X-to-Y map:` header"));
+            };
+            let numbers = text.split(" ").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
+            let mut numbers_vec: Vec<u64> = Vec::new();
+            for number in &numbers {
+                numbers_vec.push(line.number::<u64>(number)?);
+            }
+            if numbers_vec.len() != 3 {
+                let extra = numbers.get(3).copied().unwrap_or("");
+                return Err(line.error(extra, "destination, source and length"));
             }
+            stage
+                .map
+                .insert(numbers_vec[0], numbers_vec[1], numbers_vec[2])
+                .map_err(|err| line.error(text, &format!("a range that fits ({})", err)))?;
         }
 
-
-        Self {
-            seeds,
-            seed_soils,
-            soil_fers,
-            fer_waters,
-            water_lights,
-            light_temps,
-            temp_hums,
-            hum_locs,
+        if seeds.is_empty() {
+            let line = Line { number: 1, text: lines.first().map(String::as_str).unwrap_or("") };
+            return Err(line.error("", "a `seeds:` line with at least one seed"));
         }
-    }
 
-    pub fn solve(&self) -> i64 {
-        let mut min_loc: i64 = Default::default();
-
-        let stages: Vec<&Vec<Vec<u64>>> = vec![
-            &self.seed_soils,
-            &self.soil_fers,
-            &self.fer_waters,
-            &self.water_lights,
-            &self.light_temps,
-            &self.temp_hums,
-            &self.hum_locs,
-        ];
-
-        let mut locs: Vec<u64> = Default::default();
-        for seed in &self.seeds {
-            let mut loc: u64 = *seed;
-            for stage in &stages {
-                for stage_area in *stage {
-                    if loc >= stage_area[1] && loc < stage_area[1] + stage_area[2] {
-                        loc = stage_area[0] + (loc - stage_area[1]);
-                        break;
-                    }
-                }
+        for (stage, (number, text)) in stages.iter().zip(&headers) {
+            if stage.map.segments().is_empty() {
+                let line = Line { number: *number, text };
+                return Err(line.error("", "at least one `destination source length` row"));
             }
-            locs.push(loc)
         }
 
-        min_loc = *locs.iter().min().expect("should not be empty") as i64;
-
-        min_loc
+        let mut day = Self {
+            seeds,
+            stages,
+            path: Vec::new(),
+            source: String::new(),
+            target: String::new(),
+            seed_to_location: IntervalMap::new(),
+            reverse: None,
+            threads: None,
+            timeout: None,
+        };
+        let text = lines.last().map(String::as_str).unwrap_or("");
+        let line = Line { number: lines.len().max(1), text };
+        if day.path("seed", "location").is_none() {
+            return Err(line.error("", "a chain of maps from `seed` to `location`"));
+        }
+        day.select("seed", "location")
+            .map_err(|err| line.error("", &format!("maps that compose without overflowing ({})", err)))?;
+        Ok(day)
     }
 
-    pub fn solve2_bruteforce(&self) -> i64 {
-        let mut min_loc: i64 = Default::default();
-
-        let stages: Vec<&Vec<Vec<u64>>> = vec![
-            &self.seed_soils,
-            &self.soil_fers,
-            &self.fer_waters,
-            &self.water_lights,
-            &self.light_temps,
-            &self.temp_hums,
-            &self.hum_locs,
-        ];
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
 
-        let mut locs: Vec<u64> = Default::default();
-        let mut initial_seed: u64 = Default::default();
-        for (index, seed) in self.seeds.iter().enumerate() {
-            if index % 2 == 0 {
-                initial_seed = *seed;
-                continue;
+    // Breadth-first search over the categories, so the path uses as few maps as possible.
+    pub fn path(&self, source: &str, target: &str) -> Option<Vec<usize>> {
+        let mut previous: HashMap<&str, Option<usize>> = HashMap::from([(source, None)]);
+        let mut queue = VecDeque::from([source]);
+        while let Some(category) = queue.pop_front() {
+            if category == target {
+                let mut path = Vec::new();
+                let mut current = category;
+                while let Some(Some(index)) = previous.get(current) {
+                    path.push(*index);
+                    current = &self.stages[*index].source;
+                }
+                path.reverse();
+                return Some(path);
             }
 
-            for mut loc in initial_seed..(initial_seed + *seed) {
-                for stage in &stages {
-                    for stage_area in *stage {
-                        if loc >= stage_area[1] && loc < stage_area[1] + stage_area[2] {
-                            loc = stage_area[0] + (loc - stage_area[1]);
-                            break;
-                        }
-                    }
+            for (index, stage) in self.stages.iter().enumerate() {
+                if stage.source == category && !previous.contains_key(stage.target.as_str()) {
+                    previous.insert(&stage.target, Some(index));
+                    queue.push_back(&stage.target);
                 }
-                locs.push(loc)
             }
         }
 
-        min_loc = *locs.iter().min().expect("should not be empty") as i64;
+        None
+    }
+
+    // The maps from `source` to `target` composed into one.
+    pub fn map_between(&self, source: &str, target: &str) -> Result<IntervalMap, String> {
+        let path = self
+            .path(source, target)
+            .ok_or(format!("no chain of maps leads from `{}` to `{}`", source, target))?;
+        path.iter().try_fold(IntervalMap::new(), |composed, &index| composed.then(&self.stages[index].map))
+    }
 
-        min_loc
+    // E.g. `lookup("seed", "soil", 79)` for the soil that seed 79 needs.
+    pub fn lookup(&self, source: &str, target: &str, value: u64) -> Option<u64> {
+        let path = self.path(source, target)?;
+        Some(path.iter().fold(value, |value, &index| self.stages[index].map.apply(value)))
     }
 
-    fn get_range(&self, loc_range: &Vec<u64>, stage_areas: &Vec<Vec<u64>>) -> Vec<Vec<u64>> {
-        let mut new_loc_ranges: Vec<Vec<u64>> = Default::default();
+    // Makes the answers use the numbers on the `seeds:` line as `source` values and report
+    // the lowest `target`; `seed` and `location` by default.
+    pub fn select(&mut self, source: &str, target: &str) -> Result<(), String> {
+        let path = self
+            .path(source, target)
+            .ok_or(format!("no chain of maps leads from `{}` to `{}`", source, target))?;
+        self.seed_to_location = path.iter().try_fold(IntervalMap::new(), |composed, &index| composed.then(&self.stages[index].map))?;
+        self.path = path;
+        self.source = source.to_string();
+        self.target = target.to_string();
+        Ok(())
+    }
 
-        let mut stage_area: Vec<u64> = Default::default();
-        for item in stage_areas {
-            if loc_range[0] >= item[1] && loc_range[0] < item[1] + item[2] {
-                stage_area = item.clone();
-                break;
-            }
-        }
+    // All stages from the source to the target category collapsed into one map,
+    // built once while parsing.
+    pub fn seed_to_location(&self) -> &IntervalMap {
+        &self.seed_to_location
+    }
 
-        if stage_area.is_empty() {
-            stage_area = vec![loc_range[0], loc_range[0], loc_range[1] - loc_range[0] + 1]
-        }
+    pub fn location(&self, seed: u64) -> u64 {
+        self.seed_to_location.apply(seed)
+    }
 
-        if loc_range[0] >= stage_area[1] && loc_range[1] < stage_area[1] + stage_area[2] {
-            let new_loc_range: Vec<u64> = vec!(stage_area[0] + (loc_range[0] - stage_area[1]), stage_area[0] + (loc_range[1] - stage_area[1]));
-            new_loc_ranges.push(new_loc_range);
-        } else {
-            let sub_arr_left: Vec<u64> = vec!(loc_range[0], stage_area[1] + stage_area[2] - 1);
-            let sub_arr_right: Vec<u64> = vec!(stage_area[1] + stage_area[2], loc_range[1]);
+    // Walks `locations` back through every stage of the path to the seeds that end up there.
+    pub fn reverse_lookup(&self, locations: Range<u64>) -> ReverseLookup {
+        let seeds = self
+            .path
+            .iter()
+            .rev()
+            .fold(vec![locations.clone()], |ranges, &index| self.stages[index].map.preimage(&ranges));
+
+        ReverseLookup {
+            listed: self.seeds.iter().copied().filter(|seed| seeds.iter().any(|range| range.contains(seed))).collect(),
+            planted: intersect_ranges(&seeds, &self.seed_ranges()),
+            locations,
+            seeds,
+        }
+    }
 
-            for new_loc_range in self.get_range(&sub_arr_left, stage_areas) {
-                new_loc_ranges.push(new_loc_range);
-            }
-            for new_loc_range in self.get_range(&sub_arr_right, stage_areas) {
-                new_loc_ranges.push(new_loc_range);
-            }
+    pub fn solve(&self) -> i64 {
+        let mut locs: Vec<u64> = Default::default();
+        for seed in &self.seeds {
+            locs.push(self.location(*seed))
         }
 
-        new_loc_ranges
+        *locs.iter().min().expect("should not be empty") as i64
     }
 
-    fn complete_range(&self, ranges: &Vec<Vec<u64>>) -> Vec<Vec<u64>> {
-        let mut ordered_range = ranges.clone();
-        ordered_range.sort_by(|a, b| a[1].partial_cmp(&b[1]).unwrap());
+    fn threads(&self) -> usize {
+        self.threads.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+    }
 
-        let mut new_ranges: Vec<Vec<u64>> = Default::default();
-        if ordered_range[0][1] != 0 {
-            new_ranges.push(vec![0, 0, ordered_range[0][1]]);
-        }
-        let mut prev_range = ordered_range[0].clone();
-        for range in &ordered_range[1..ordered_range.len()] {
-            new_ranges.push(prev_range.clone());
+    pub fn solve2_bruteforce(&self) -> i64 {
+        self.solve2_bruteforce_with(self.threads(), |_, _| {}, &AtomicBool::new(false))
+            .expect("should not be empty") as i64
+    }
 
-            if prev_range[1] + prev_range[2] != range[1] {
-                new_ranges.push(vec![prev_range[1] + prev_range[2], prev_range[1] + prev_range[2], range[1] - (prev_range[1] + prev_range[2])]);
+    // Maps every seed one by one on `threads` workers, which take `BRUTEFORCE_CHUNK` seeds at a time.
+    // `progress` gets the number of seeds done so far and the total after every chunk; setting
+    // `cancel` stops the workers after their current chunk and makes the result `None`.
+    pub fn solve2_bruteforce_with(
+        &self,
+        threads: usize,
+        progress: impl Fn(u64, u64) + Sync,
+        cancel: &AtomicBool,
+    ) -> Option<u64> {
+        let chunks: Vec<Range<u64>> = self
+            .seed_ranges()
+            .into_iter()
+            .flat_map(|range| {
+                (range.start..range.end)
+                    .step_by(BRUTEFORCE_CHUNK as usize)
+                    .map(move |start| start..range.end.min(start + BRUTEFORCE_CHUNK))
+            })
+            .collect();
+        let total = chunks.iter().map(|chunk| chunk.end - chunk.start).sum();
+        let next = AtomicUsize::new(0);
+        let done = AtomicU64::new(0);
+        let lowest = AtomicU64::new(u64::MAX);
+
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                scope.spawn(|| {
+                    while !cancel.load(Ordering::Relaxed) {
+                        let Some(chunk) = chunks.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                            break;
+                        };
+                        let mut min = u64::MAX;
+                        for seed in chunk.clone() {
+                            min = min.min(self.path.iter().fold(seed, |loc, &index| self.stages[index].map.apply(loc)));
+                        }
+                        lowest.fetch_min(min, Ordering::Relaxed);
+                        progress(done.fetch_add(chunk.end - chunk.start, Ordering::Relaxed) + chunk.end - chunk.start, total);
+                    }
+                });
             }
+        });
 
-            prev_range = range.clone();
+        if cancel.load(Ordering::Relaxed) || total == 0 {
+            return None;
         }
-        new_ranges.push(ordered_range[ordered_range.len() - 1].clone());
+        Some(lowest.into_inner())
+    }
 
-        return new_ranges;
+    // The `seeds:` line read as pairs of start and length, which parsing checked to be
+    // complete, non-empty and within u64.
+    pub fn seed_ranges(&self) -> Vec<Range<u64>> {
+        self.seeds.chunks(2).map(|pair| pair[0]..pair[0] + pair[1]).collect()
     }
 
     pub fn solve2(&self) -> i64 {
-        let mut min_loc: i64 = Default::default();
-
-        let complete_seed_soils = self.complete_range(&self.seed_soils);
-        let complete_soil_fers = self.complete_range(&self.soil_fers);
-        let complete_fer_waters = self.complete_range(&self.fer_waters);
-        let complete_water_lights = self.complete_range(&self.water_lights);
-        let complete_light_temps = self.complete_range(&self.light_temps);
-        let complete_temp_hums = self.complete_range(&self.temp_hums);
-        let complete_hum_locs = self.complete_range(&self.hum_locs);
-
-        let stages: Vec<&Vec<Vec<u64>>> = vec![
-            &complete_seed_soils,
-            &complete_soil_fers,
-            &complete_fer_waters,
-            &complete_water_lights,
-            &complete_light_temps,
-            &complete_temp_hums,
-            &complete_hum_locs,
-        ];
-
-        let mut initial_seed: u64 = Default::default();
-        let mut loc_ranges: Vec<Vec<u64>> = Default::default();
-        for (index, seed) in self.seeds.iter().enumerate() {
-            if index % 2 == 0 {
-                initial_seed = *seed;
-                continue;
-            } else {
-                loc_ranges.push(vec![initial_seed, initial_seed + *seed])
-            }
-        }
+        let loc_ranges = self.seed_to_location.apply_ranges(&self.seed_ranges());
+        loc_ranges[0].start as i64
+    }
+}
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
 
-        for stage in stages {
-            let mut new_loc_ranges: Vec<Vec<u64>> = Default::default();
-            for loc_range in loc_ranges.iter() {
-                for item in self.get_range(loc_range, stage) {
-                    new_loc_ranges.push(item);
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
+    }
+
+    fn report(&self) -> Option<String> {
+        let mut report = format!("{}-to-{} map:\n{}", self.source, self.target, self.seed_to_location);
+
+        // Without a `reverse` setting, explain where the part 2 answer comes from.
+        let lowest = self.solve2() as u64;
+        let lookup = self.reverse_lookup(self.reverse.clone().unwrap_or(lowest..lowest + 1));
+        report += &format!(
+            "\n{} {:?} come from {} {:?}\nlisted on the `seeds:` line: {:?}\ninside the `seeds:` ranges: {:?}\n",
+            self.target, lookup.locations, self.source, lookup.seeds, lookup.listed, lookup.planted
+        );
+        Some(report)
+    }
+
+    fn configure(&mut self, key: &str, value: &str) -> Result<Effect, String> {
+        match key {
+            "source" => self.select(value, &self.target.clone()).map(|()| Effect::ChangesAnswers),
+            "target" => self.select(&self.source.clone(), value).map(|()| Effect::ChangesAnswers),
+            "reverse" => {
+                let range = match value.split_once("..") {
+                    Some((start, end)) => start.parse::<u64>().ok().zip(end.parse::<u64>().ok()).map(|(start, end)| start..end),
+                    None => value.parse::<u64>().ok().and_then(|value| Some(value..value.checked_add(1)?)),
+                };
+                let range = range.ok_or(format!("`{}` is not a number below u64::MAX or a range like 40..50", value))?;
+                if range.start >= range.end {
+                    return Err(format!("`{}` is an empty range, the end has to be above the start", value));
                 }
+                self.reverse = Some(range);
+                Ok(Effect::ChangesReport)
+            }
+            "threads" => {
+                let threads = value.parse::<usize>().ok().filter(|&threads| threads > 0);
+                self.threads = Some(threads.ok_or(format!("`{}` is not a positive number of threads", value))?);
+                Ok(Effect::KeepsAnswers)
+            }
+            "timeout" => {
+                let timeout = value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                    .filter(|timeout| !timeout.is_zero());
+                self.timeout = Some(timeout.ok_or(format!("`{}` is not a positive number of seconds", value))?);
+                Ok(Effect::KeepsAnswers)
             }
-            loc_ranges = new_loc_ranges;
+            _ => Err(format!("unknown option `{}`, expected source, target, reverse, threads or timeout", key)),
         }
+    }
 
-        let mut ordered_range = loc_ranges.clone();
-        ordered_range.sort_by(|a, b| a[1].partial_cmp(&b[1]).unwrap());
-
-        min_loc = ordered_range[0][0] as i64;
-        min_loc
+    fn variants(&self) -> Vec<Variant<'_>> {
+        vec![Variant {
+            part: 2,
+            name: "bruteforce",
+            run: Box::new(|| {
+                // Whole percents on stderr, so the table on stdout stays clean.
+                let shown = AtomicU64::new(0);
+                let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+                let cancel = AtomicBool::new(false);
+                let progress = |done: u64, total: u64| {
+                    let percent = done * 100 / total;
+                    if shown.fetch_max(percent, Ordering::Relaxed) < percent {
+                        eprint!("\rday 5 bruteforce: {}%", percent);
+                    }
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                };
+                let lowest = self.solve2_bruteforce_with(self.threads(), progress, &cancel);
+                if shown.load(Ordering::Relaxed) > 0 {
+                    eprintln!();
+                }
+                match lowest {
+                    Some(lowest) => Ok((lowest as i64).into()),
+                    None => Err(Failure::Cancelled(format!("stopped after the {:?} timeout", self.timeout.unwrap_or_default()))),
+                }
+            }),
+        }]
     }
 }
@@ -1,6 +1,7 @@
-use std::fs::File;
-use std::hash::Hash;
-use std::io::{BufRead, BufReader};
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use crate::solution::{Answer, Failure, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
 
 pub struct Day {
     times: Vec<u64>,
@@ -10,43 +11,62 @@ pub struct Day {
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).expect("should be able to open the file");
-        let reader = BufReader::new(file);
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
 
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
         let mut times = Vec::new();
         let mut distances = Vec::new();
-        let mut merged_time: String = String::default();
-        let mut merged_distance: String = String::default();
-        for line_res in reader.lines() {
-            let line = line_res.expect("should be able to read the string");
+        let mut merged_time: Option<u64> = None;
+        let mut merged_distance: Option<u64> = None;
+        let lines = read_lines(reader)?;
+        for (index, text) in lines.iter().enumerate() {
+            let line = Line { number: index + 1, text };
 
-            let parts = line.split(" ").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
+            let parts = text.split(" ").filter(|x| !x.is_empty()).collect::<Vec<&str>>();
 
-            match parts[0] {
-                "Time:" => {
+            match parts.first().copied() {
+                Some("Time:") => {
+                    let mut merged: String = String::default();
                     for part in &parts[1..] {
-                        times.push(part.parse::<u64>().expect("should number"));
-                        merged_time += part;
+                        times.push(line.number::<u64>(part)?);
+                        merged += part;
                     }
+                    merged_time = Some(merged.parse::<u64>().map_err(|_| line.error(text, "a merged time within 64 bits"))?);
                 }
-                "Distance:" => {
+                Some("Distance:") => {
+                    let mut merged: String = String::default();
                     for part in &parts[1..] {
-                        distances.push(part.parse::<u64>().expect("should number"));
-                        merged_distance += part;
+                        distances.push(line.number::<u64>(part)?);
+                        merged += part;
                     }
+                    merged_distance = Some(merged.parse::<u64>().map_err(|_| line.error(text, "a merged distance within 64 bits"))?);
                 }
-                _ => {}
+                Some(part) => return Err(line.error(part, "`Time:` or `Distance:`")),
+                None => {}
             }
         }
 
+        let last_line = Line {
+            number: lines.len().max(1),
+            text: lines.last().map(String::as_str).unwrap_or(""),
+        };
+        let (merged_time, merged_distance) = match (merged_time, merged_distance) {
+            (Some(time), Some(distance)) => (time, distance),
+            (None, _) => return Err(last_line.error("", "a `Time:` line")),
+            (_, None) => return Err(last_line.error("", "a `Distance:` line")),
+        };
+        if times.len() != distances.len() {
+            return Err(last_line.error("", &format!("{} distances to match the times", times.len())));
+        }
 
-        Self {
+        Ok(Self {
             times,
             distances,
-            merged_time: merged_time.parse::<u64>().expect("should number"),
-            merged_distance: merged_distance.parse::<u64>().expect("should number"),
-        }
+            merged_time,
+            merged_distance,
+        })
     }
 
     pub fn solve(&self) -> i64 {
@@ -67,8 +87,6 @@ impl Day {
     }
 
     pub fn solve2(&self) -> i64 {
-        let mut total: i64 = 1;
-
         let mut left_pointer: u64 = 0;
         let mut right_pointer: u64 = self.merged_time;
         let mut is_left_found: bool = false;
@@ -92,7 +110,28 @@ impl Day {
             }
         }
 
-        total = (right_pointer - left_pointer) as i64 + 1;
-        total
+        (right_pointer - left_pointer) as i64 + 1
+    }
+}
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
     }
 }
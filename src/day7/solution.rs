@@ -1,9 +1,10 @@
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use crate::solution::{Answer, Failure, Solution};
 use std::cmp;
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::fs::File;
-use std::hash::Hash;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use std::str::FromStr;
 
 pub struct Day {
     hands: Vec<Hand>,
@@ -18,11 +19,7 @@ pub struct Hand {
 
 impl PartialEq<Self> for Hand {
     fn eq(&self, other: &Self) -> bool {
-        if self.hand == other.hand {
-            true
-        } else {
-            false
-        }
+        self.hand == other.hand
     }
 }
 
@@ -36,8 +33,8 @@ impl PartialOrd<Self> for Hand {
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
-        let hand1: &Hand = &(self);
-        let hand2: &Hand = &(other);
+        let hand1: &Hand = self;
+        let hand2: &Hand = other;
 
         if hand1.score > hand2.score {
             return Ordering::Greater;
@@ -70,11 +67,7 @@ pub struct Hand2 {
 
 impl PartialEq<Self> for Hand2 {
     fn eq(&self, other: &Self) -> bool {
-        if self.hand == other.hand {
-            true
-        } else {
-            false
-        }
+        self.hand == other.hand
     }
 }
 
@@ -88,8 +81,8 @@ impl PartialOrd<Self> for Hand2 {
 
 impl Ord for Hand2 {
     fn cmp(&self, other: &Self) -> Ordering {
-        let hand1: &Hand2 = &(self);
-        let hand2: &Hand2 = &(other);
+        let hand1: &Hand2 = self;
+        let hand2: &Hand2 = other;
 
         if hand1.score > hand2.score {
             return Ordering::Greater;
@@ -114,30 +107,38 @@ impl Ord for Hand2 {
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Self {
-        let file = File::open(input_file).expect("should be able to open the file");
-        let reader = BufReader::new(file);
+    pub fn new(input_file: &str) -> Result<Self, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
 
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
         let mut hands: Vec<Hand> = Default::default();
-        for line_res in reader.lines() {
-            let line = line_res.expect("should be able to read the string");
+        for (index, text) in read_lines(reader)?.iter().enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            let line = Line { number: index + 1, text };
 
-            let hand_bid = line
+            let hand_bid = text
                 .split(" ")
                 .filter(|&x| !x.is_empty())
                 .collect::<Vec<&str>>();
-            let bid = hand_bid[1].parse::<u64>().expect("should be a number");
+            let cards = line.field(&hand_bid, 0, "a hand")?;
+            if cards.chars().count() != 5 || !cards.chars().all(|card| "23456789TJQKA".contains(card)) {
+                return Err(line.error(cards, "five cards out of `23456789TJQKA`"));
+            }
+            let bid = line.number::<u64>(line.field(&hand_bid, 1, "a bid")?)?;
 
             let hand = Hand {
-                hand: hand_bid[0].to_string(),
+                hand: cards.to_string(),
                 score: 0,
-                bid: bid,
+                bid,
             };
 
             hands.push(hand);
         }
 
-        Self { hands }
+        Ok(Self { hands })
     }
 
     pub fn solve(&self) -> u64 {
@@ -243,7 +244,7 @@ fn get_score2(hand: &str) -> u8 {
     let mut jocker_count: u8 = Default::default();
     for char in hand.chars() {
         if char == 'J' {
-            jocker_count = jocker_count + 1;
+            jocker_count += 1;
         }
     }
 
@@ -358,3 +359,25 @@ fn get_card_strength2(char: char) -> u8 {
         14
     }
 }
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
+    }
+}
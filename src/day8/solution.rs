@@ -1,6 +1,9 @@
+use crate::math::lcm;
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use crate::solution::{Answer, Failure, Solution};
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
+use std::str::FromStr;
 
 pub struct Day {
     commands: Vec<char>,
@@ -14,34 +17,53 @@ pub struct Node {
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Day {
-        let file = File::open(input_file.trim()).unwrap();
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
+    pub fn new(input_file: &str) -> Result<Day, ParseError> {
+        read_file(input_file.trim(), Self::from_reader)
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Day, ParseError> {
+        let input = read_lines(reader)?;
+        let mut lines = input.iter().enumerate();
 
-        let commands: Vec<char> = lines.next().unwrap().unwrap().chars().collect();
+        let commands: Vec<char> = match lines.next() {
+            Some((_, text)) => {
+                let line = Line { number: 1, text };
+                if text.is_empty() {
+                    return Err(line.error("", "a list of `L`/`R` instructions"));
+                }
+                if let Some(position) = text.find(|c| c != 'L' && c != 'R') {
+                    return Err(line.error(&text[position..position + 1], "`L` or `R`"));
+                }
+                text.chars().collect()
+            }
+            None => {
+                let line = Line { number: 1, text: "" };
+                return Err(line.error("", "a list of `L`/`R` instructions"));
+            }
+        };
         let mut nodes = HashMap::new();
-        for line_result in lines {
-            let line = line_result.unwrap();
-            if line == "".to_string() {
+        for (index, text) in lines {
+            if text.is_empty() {
                 continue;
             }
+            let line = Line { number: index + 1, text };
 
-            let first_split = line
+            let first_split = text
                 .split(" = (")
                 .filter(|&x| !x.is_empty())
                 .collect::<Vec<&str>>();
-            let second_split = first_split[1]
+            let second_split = line
+                .field(&first_split, 1, "` = (`")?
                 .split(", ")
                 .filter(|&x| !x.is_empty())
                 .collect::<Vec<&str>>();
 
             let head = first_split[0].to_string();
-            let left = second_split[0].to_string();
-            let right = second_split[1]
-                .to_string()
+            let left = line.field(&second_split, 0, "a left node")?.to_string();
+            let right_part = line.field(&second_split, 1, "`, ` followed by a right node")?;
+            let right = right_part
                 .strip_suffix(")")
-                .unwrap()
+                .ok_or_else(|| line.error(right_part, "a right node followed by `)`"))?
                 .to_string();
             let dead_end = (head.as_str() == left.as_str()) && (head.as_str() == right.as_str());
             nodes.insert(
@@ -54,7 +76,7 @@ impl Day {
             );
         }
 
-        Day { commands, nodes }
+        Ok(Day { commands, nodes })
     }
 
     pub fn solve(&self) -> u64 {
@@ -65,14 +87,14 @@ impl Day {
         let finish_node = "ZZZ".to_string();
         loop {
             if self.nodes.contains_key(current_node.as_str()) {
-                if self.nodes.get(current_node.as_str()).unwrap().dead_end == true {
+                if self.nodes.get(current_node.as_str()).unwrap().dead_end {
                     break;
                 }
 
                 current_node = match self.commands[current_index_command] {
                     'R' => self.nodes.get(&current_node).unwrap().right.clone(),
                     'L' => self.nodes.get(&current_node).unwrap().left.clone(),
-                    _ => todo!(),
+                    _ => unreachable!("the parser only keeps `L` and `R`"),
                 };
                 step += 1;
 
@@ -96,7 +118,7 @@ impl Day {
 
         let mut current_nodes: Vec<String> = Default::default();
         let mut finish_nodes: Vec<String> = Default::default();
-        for (head, node) in &self.nodes {
+        for head in self.nodes.keys() {
             if head.ends_with("A") {
                 current_nodes.push(head.clone())
             }
@@ -109,14 +131,14 @@ impl Day {
             let mut finish_count: u32 = Default::default();
             for current_node in current_nodes.iter_mut() {
                 if self.nodes.contains_key(current_node.as_str()) {
-                    if self.nodes.get(current_node.as_str()).unwrap().dead_end == true {
+                    if self.nodes.get(current_node.as_str()).unwrap().dead_end {
                         break;
                     }
 
                     *current_node = match self.commands[current_index_command] {
                         'R' => self.nodes.get(current_node).unwrap().right.clone(),
                         'L' => self.nodes.get(current_node).unwrap().left.clone(),
-                        _ => todo!(),
+                        _ => unreachable!("the parser only keeps `L` and `R`"),
                     };
 
                     if finish_nodes.contains(current_node) {
@@ -147,7 +169,7 @@ impl Day {
         let mut start_node_current_nodes: HashMap<String, String> = Default::default();
         let mut start_node_finish_step: HashMap<String, u64> = Default::default();
         let mut finish_nodes: Vec<String> = Default::default();
-        for (head, node) in &self.nodes {
+        for head in self.nodes.keys() {
             if head.ends_with("A") {
                 start_node_current_nodes.insert(head.clone(), head.clone());
                 start_node_finish_step.insert(head.clone(), 0);
@@ -165,14 +187,14 @@ impl Day {
                 }
 
                 if self.nodes.contains_key(current_node.as_str()) {
-                    if self.nodes.get(current_node.as_str()).unwrap().dead_end == true {
+                    if self.nodes.get(current_node.as_str()).unwrap().dead_end {
                         break;
                     }
 
                     *current_node = match self.commands[current_index_command] {
                         'R' => self.nodes.get(current_node).unwrap().right.clone(),
                         'L' => self.nodes.get(current_node).unwrap().left.clone(),
-                        _ => todo!(),
+                        _ => unreachable!("the parser only keeps `L` and `R`"),
                     };
 
                     let finish_step = start_node_finish_step.get_mut(start_node).unwrap();
@@ -199,22 +221,31 @@ impl Day {
                 continue;
             }
 
-            step = self.lcm(step, *finish_step);
+            step = lcm(step, *finish_step);
         }
 
         step
     }
+}
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
 
-    fn gcd(&self, mut a: u64, mut b: u64) -> u64 {
-        while b != 0 {
-            let r = a % b;
-            a = b;
-            b = r;
-        }
-        a
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
     }
 
-    fn lcm(&self, a: u64, b: u64) -> u64 {
-        (a * b) / self.gcd(a, b)
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
     }
 }
@@ -1,30 +1,41 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::parse::{read_file, read_lines, Line, ParseError};
+use crate::solution::{Answer, Failure, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
 
 pub struct Day {
     histories: Vec<Vec<i64>>,
 }
 
 impl Day {
-    pub fn new(input_file: &str) -> Day {
-        let file = File::open(input_file).unwrap();
-        let reader = BufReader::new(file);
-        let lines = reader.lines();
+    pub fn new(input_file: &str) -> Result<Day, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
 
+    pub fn from_reader(reader: impl BufRead) -> Result<Day, ParseError> {
         let mut histories: Vec<Vec<i64>> = Default::default();
-        for line in lines {
-            let line_res = line.unwrap();
-            let parsed_line: Vec<i64> = line_res
+        for (index, text) in read_lines(reader)?.iter().enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            let line = Line { number: index + 1, text };
+
+            let parsed_line: Vec<i64> = text
                 .split(" ")
-                .filter_map(|x| x.parse::<i64>().ok())
-                .collect();
+                .filter(|x| !x.is_empty())
+                .map(|x| line.number::<i64>(x))
+                .collect::<Result<Vec<i64>, ParseError>>()?;
+            // The differences of a single value are empty, so there is nothing to extrapolate from.
+            if parsed_line.len() < 2 {
+                return Err(line.error("", "a history of at least two numbers"));
+            }
             histories.push(parsed_line);
         }
 
-        Day { histories }
+        Ok(Day { histories })
     }
 
-    pub fn solve(&self) -> u64 {
+    pub fn solve(&self) -> i64 {
         let mut nexts: Vec<i64> = Default::default();
 
         for history in &self.histories {
@@ -42,11 +53,10 @@ impl Day {
             nexts.push(last_number_sum);
         }
 
-        let total: i64 = nexts.iter().sum();
-        total as u64
+        nexts.iter().sum()
     }
 
-    pub fn solve2(&self) -> u64 {
+    pub fn solve2(&self) -> i64 {
         let mut prevs: Vec<i64> = Default::default();
 
         for history in &self.histories {
@@ -67,7 +77,28 @@ impl Day {
             prevs.push(first_number_fold);
         }
 
-        let total: i64 = prevs.iter().sum();
-        total as u64
+        prevs.iter().sum()
+    }
+}
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
     }
 }
@@ -0,0 +1,269 @@
+use crate::parse::{read_lines, Line, ParseError};
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+const OFFSETS8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // Fails with the index of the first row whose length differs from the first one.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, usize> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            return Err(index);
+        }
+
+        let height = rows.len();
+        Ok(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // Parses one cell per character; `cell` gets the line and the character as a slice of it,
+    // so it can point errors at the offending tile. Empty lines are skipped.
+    pub fn from_reader(
+        reader: impl BufRead,
+        mut cell: impl FnMut(&Line, &str) -> Result<T, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for (index, text) in read_lines(reader)?.iter().enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            let line = Line { number: index + 1, text };
+
+            let row = text
+                .char_indices()
+                .map(|(position, c)| cell(&line, &text[position..position + c.len_utf8()]))
+                .collect::<Result<Vec<T>, ParseError>>()?;
+            if let Some(first_row) = rows.first() {
+                if first_row.len() != row.len() {
+                    return Err(line.error(text, &format!("a row of {} tiles", first_row.len())));
+                }
+            }
+            rows.push(row);
+        }
+
+        Ok(Self::from_rows(rows).unwrap_or_else(|_| unreachable!("rows are checked while parsing")))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    // The position `d_row` rows and `d_col` columns away, if it is inside the grid.
+    pub fn offset(&self, pos: Pos, d_row: isize, d_col: isize) -> Option<Pos> {
+        let row = pos.row.checked_add_signed(d_row)?;
+        let col = pos.col.checked_add_signed(d_col)?;
+        let next = Pos::new(row, col);
+        if self.contains(next) {
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        let (d_row, d_col) = direction.offset();
+        self.offset(pos, d_row, d_col)
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL.into_iter().filter_map(move |direction| self.step(pos, direction))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS8
+            .into_iter()
+            .filter_map(move |(d_row, d_col)| self.offset(pos, d_row, d_col))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Pos::new(row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` happy for an empty grid, which has no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1)).take(self.height)
+    }
+
+    // The cells from `from` to `to` (both inclusive), clipped to the grid.
+    pub fn region(&self, from: Pos, to: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        let rows = from.row..=to.row.min(self.height.saturating_sub(1));
+        let cols = from.col..=to.col.min(self.width.saturating_sub(1));
+        rows.flat_map(move |row| cols.clone().map(move |col| Pos::new(row, col)))
+            .filter_map(move |pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(usize, usize) -> Pos) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| self[source(row, col)].clone())
+            .collect();
+        Self { width, height, cells }
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.rebuild(self.height, self.width, |row, col| Pos::new(col, row))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height;
+        self.rebuild(self.height, self.width, |row, col| Pos::new(height - 1 - col, row))
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        let width = self.width;
+        self.rebuild(self.height, self.width, |row, col| Pos::new(col, width - 1 - row))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, width, height))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Grid::from_reader(input.as_bytes(), |_, tile| Ok(tile.chars().next().unwrap_or_default()))
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::answers::input_hash;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub const YEAR: u32 = 2023;
+pub const DEFAULT_CACHE_DIR: &str = "inputs";
+pub const DEFAULT_SESSION_FILE: &str = ".session";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+// Where a puzzle input comes from when it is not in the cache yet.
+pub trait Fetcher {
+    // Names the account the input belongs to; every account gets its own input.
+    fn user(&self) -> String;
+    fn fetch(&self, year: u32, day: u32) -> Result<Vec<u8>, String>;
+
+    // A fetcher that reads local files hands out the file itself, so it is read fresh on every
+    // run instead of from a copy in the cache.
+    fn source_path(&self, _day: u32) -> Option<PathBuf> {
+        None
+    }
+}
+
+// Downloads inputs from adventofcode.com with the session cookie of a logged in browser.
+pub struct SessionFetcher {
+    token: String,
+    base_url: String,
+}
+
+impl SessionFetcher {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.trim().to_string(),
+            base_url: "https://adventofcode.com".to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    // The token comes from `AOC_SESSION`, or else from `session_file`.
+    pub fn from_env(session_file: impl AsRef<Path>) -> Result<Option<Self>, String> {
+        if let Ok(token) = env::var(SESSION_VAR) {
+            if !token.trim().is_empty() {
+                return Ok(Some(Self::new(&token)));
+            }
+        }
+
+        let session_file = session_file.as_ref();
+        match fs::read_to_string(session_file) {
+            Ok(token) if !token.trim().is_empty() => Ok(Some(Self::new(&token))),
+            Ok(_) => Ok(None),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("cannot read {}: {}", session_file.display(), err)),
+        }
+    }
+
+    pub fn url(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+}
+
+impl Fetcher for SessionFetcher {
+    // The token itself must not end up in a path, only a hash of it.
+    fn user(&self) -> String {
+        input_hash(self.token.as_bytes())
+    }
+
+    fn fetch(&self, year: u32, day: u32) -> Result<Vec<u8>, String> {
+        let url = self.url(year, day);
+        // The cookie goes in through a config on stdin, so the token never shows up in `ps`.
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--location"])
+            .args(["--user-agent", "advent-of-code-2023 input fetcher"])
+            .args(["--config", "-"])
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("cannot run curl to download {}: {}", url, err))?;
+        let token = self.token.replace('\\', "\\\\").replace('"', "\\\"");
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(format!("cookie = \"session={}\"\n", token).as_bytes())
+            .map_err(|err| format!("cannot pass the session to curl: {}", err))?;
+        let output = child
+            .wait_with_output()
+            .map_err(|err| format!("cannot run curl to download {}: {}", url, err))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "cannot download {}: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(output.stdout)
+    }
+}
+
+// Reads inputs laid out as `<root>/day<N>/input.txt`, like the ones bundled in `src`.
+pub struct LocalFetcher {
+    root: PathBuf,
+}
+
+impl LocalFetcher {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.root.join(format!("day{}", day)).join("input.txt")
+    }
+}
+
+impl Fetcher for LocalFetcher {
+    fn user(&self) -> String {
+        "local".to_string()
+    }
+
+    fn fetch(&self, _year: u32, day: u32) -> Result<Vec<u8>, String> {
+        let path = self.path(day);
+        fs::read(&path).map_err(|err| format!("cannot read {}: {}", path.display(), err))
+    }
+
+    fn source_path(&self, day: u32) -> Option<PathBuf> {
+        Some(self.path(day))
+    }
+}
+
+// Resolves the input of a day to a file in the cache, fetching it the first time, or to the
+// fetcher's own file when it has one.
+pub struct InputManager {
+    cache_dir: PathBuf,
+    year: u32,
+    fetcher: Box<dyn Fetcher>,
+}
+
+impl InputManager {
+    pub fn new(cache_dir: impl AsRef<Path>, fetcher: Box<dyn Fetcher>) -> Self {
+        Self {
+            cache_dir: cache_dir.as_ref().to_path_buf(),
+            year: YEAR,
+            fetcher,
+        }
+    }
+
+    pub fn with_year(mut self, year: u32) -> Self {
+        self.year = year;
+        self
+    }
+
+    // <cache>/<year>/<user>/day<N>.txt
+    pub fn cache_path(&self, day: u32) -> PathBuf {
+        self.cache_dir
+            .join(self.year.to_string())
+            .join(self.fetcher.user())
+            .join(format!("day{}.txt", day))
+    }
+
+    pub fn resolve(&self, day: u32) -> Result<PathBuf, String> {
+        if let Some(path) = self.fetcher.source_path(day) {
+            return Ok(path);
+        }
+
+        let path = self.cache_path(day);
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        let input = self.fetcher.fetch(self.year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| format!("cannot create {}: {}", dir.display(), err))?;
+        }
+        fs::write(&path, input).map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+use std::ops::Range;
+
+// Every `x` in `start..end` maps to `x + offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: u64,
+    pub end: u64,
+    pub offset: i64,
+}
+
+impl Segment {
+    pub fn apply(&self, x: u64) -> u64 {
+        x.wrapping_add_signed(self.offset)
+    }
+
+    pub fn image(&self) -> Range<u64> {
+        self.apply(self.start)..self.apply(self.end)
+    }
+}
+
+// A piecewise shift of `0..u64::MAX`: values inside a segment move by its offset,
+// everything else maps to itself. Segments are kept sorted and never overlap, and their
+// offsets stay within `-i64::MAX..=i64::MAX` so they can always be negated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalMap {
+    segments: Vec<Segment>,
+}
+
+impl IntervalMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    // Adds the mapping of `len` values from `source` to `destination`, as the almanac lists them.
+    pub fn insert(&mut self, destination: u64, source: u64, len: u64) -> Result<(), String> {
+        let end = source
+            .checked_add(len)
+            .filter(|_| destination.checked_add(len).is_some())
+            .ok_or(format!("{}..{}+{} does not fit in u64", source, source, len))?;
+        if len == 0 {
+            return Ok(());
+        }
+
+        let index = self.segments.partition_point(|segment| segment.end <= source);
+        if let Some(next) = self.segments.get(index) {
+            if next.start < end {
+                return Err(format!("{}..{} overlaps {}..{}", source, end, next.start, next.end));
+            }
+        }
+
+        let offset = i64::try_from(destination as i128 - source as i128)
+            .ok()
+            .filter(|&offset| offset != i64::MIN)
+            .ok_or(format!("{} to {} is too far a shift", source, destination))?;
+        self.segments.insert(index, Segment { start: source, end, offset });
+        Ok(())
+    }
+
+    fn segment_at(&self, x: u64) -> Option<&Segment> {
+        let index = self.segments.partition_point(|segment| segment.end <= x);
+        self.segments.get(index).filter(|segment| segment.start <= x)
+    }
+
+    pub fn apply(&self, x: u64) -> u64 {
+        self.segment_at(x).map_or(x, |segment| segment.apply(x))
+    }
+
+    // The same map with the gaps between segments filled by explicit identity segments,
+    // so the segments cover the whole domain. Ends are exclusive, so `u64::MAX` itself is
+    // never inside a segment and always maps to itself, as it does in `apply`.
+    pub fn fill_gaps(&self) -> IntervalMap {
+        let mut segments = Vec::with_capacity(self.segments.len() * 2 + 1);
+        let mut next = 0;
+        for segment in &self.segments {
+            if next < segment.start {
+                segments.push(Segment { start: next, end: segment.start, offset: 0 });
+            }
+            segments.push(*segment);
+            next = segment.end;
+        }
+        if next < u64::MAX {
+            segments.push(Segment { start: next, end: u64::MAX, offset: 0 });
+        }
+
+        IntervalMap { segments }
+    }
+
+    // Drops identity segments and merges neighbours that shift by the same amount.
+    fn normalized(mut segments: Vec<Segment>) -> IntervalMap {
+        segments.sort_by_key(|segment| segment.start);
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments.into_iter().filter(|segment| segment.offset != 0 && segment.start < segment.end) {
+            match merged.last_mut() {
+                Some(last) if last.end == segment.start && last.offset == segment.offset => last.end = segment.end,
+                _ => merged.push(segment),
+            }
+        }
+
+        IntervalMap { segments: merged }
+    }
+
+    // The images of `ranges`, sorted and with overlapping or touching ranges merged.
+    pub fn apply_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let filled = self.fill_gaps();
+        let mut images: Vec<Range<u64>> = Vec::new();
+        for range in ranges {
+            let first = filled.segments.partition_point(|segment| segment.end <= range.start);
+            for segment in filled.segments[first..].iter().take_while(|segment| segment.start < range.end) {
+                let start = range.start.max(segment.start);
+                let end = range.end.min(segment.end);
+                if start < end {
+                    images.push(segment.apply(start)..segment.apply(end));
+                }
+            }
+        }
+
+        merge_ranges(images)
+    }
+
+    // Every value that lands in one of `ranges`, also for maps that are not invertible.
+    pub fn preimage(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut sources: Vec<Range<u64>> = Vec::new();
+        for segment in self.fill_gaps().segments {
+            let image = segment.image();
+            for range in ranges {
+                let start = range.start.max(image.start);
+                let end = range.end.min(image.end);
+                if start < end {
+                    sources.push(start.wrapping_add_signed(-segment.offset)..end.wrapping_add_signed(-segment.offset));
+                }
+            }
+        }
+
+        merge_ranges(sources)
+    }
+
+    // The map that applies `self` and then `next`; fails when a value would move further
+    // than an offset can hold.
+    pub fn then(&self, next: &IntervalMap) -> Result<IntervalMap, String> {
+        let next = next.fill_gaps();
+        let mut segments = Vec::new();
+        for segment in self.fill_gaps().segments {
+            let image = segment.image();
+            let first = next.segments.partition_point(|other| other.end <= image.start);
+            for other in next.segments[first..].iter().take_while(|other| other.start < image.end) {
+                let start = image.start.max(other.start);
+                let end = image.end.min(other.end);
+                if start < end {
+                    let (start, end) = (start.wrapping_add_signed(-segment.offset), end.wrapping_add_signed(-segment.offset));
+                    let offset = segment
+                        .offset
+                        .checked_add(other.offset)
+                        .filter(|&offset| offset != i64::MIN)
+                        .ok_or(format!("{}..{} shifts by {} and then {}, too far in total", start, end, segment.offset, other.offset))?;
+                    segments.push(Segment { start, end, offset });
+                }
+            }
+        }
+
+        Ok(Self::normalized(segments))
+    }
+
+    // `None` when two values map to the same one, so there is no inverse.
+    pub fn invert(&self) -> Option<IntervalMap> {
+        let mut images: Vec<Segment> = self
+            .fill_gaps()
+            .segments
+            .iter()
+            .map(|segment| {
+                let image = segment.image();
+                Segment { start: image.start, end: image.end, offset: -segment.offset }
+            })
+            .collect();
+        images.sort_by_key(|segment| segment.start);
+        if images.windows(2).any(|pair| pair[0].end > pair[1].start) {
+            return None;
+        }
+
+        Some(Self::normalized(images))
+    }
+}
+
+// One `destination source length` row per segment, the way the almanac writes its maps.
+impl fmt::Display for IntervalMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            writeln!(f, "{} {} {}", segment.apply(segment.start), segment.start, segment.end - segment.start)?;
+        }
+        Ok(())
+    }
+}
+
+// The values that are in both `a` and `b`, merged the same way.
+pub fn intersect_ranges(a: &[Range<u64>], b: &[Range<u64>]) -> Vec<Range<u64>> {
+    let mut common = Vec::new();
+    for x in a {
+        for y in b {
+            common.push(x.start.max(y.start)..x.end.min(y.end));
+        }
+    }
+    merge_ranges(common)
+}
+
+pub fn merge_ranges(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    ranges.retain(|range| range.start < range.end);
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
@@ -0,0 +1,23 @@
+pub mod aho_corasick;
+pub mod answers;
+pub mod bench;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day24;
+pub mod grid;
+pub mod inputs;
+pub mod interval;
+pub mod math;
+pub mod parse;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
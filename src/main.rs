@@ -1,21 +1,18 @@
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day24;
-
-use day10::solution;
+use advent_of_code_2023::runner;
+use std::env;
+use std::process;
 
 fn main() {
-    let day = solution::Day::new("./src/day10/input.txt");
-
-    let solution = day.solve();
+    let args = match runner::Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, runner::USAGE);
+            process::exit(2);
+        }
+    };
 
-    println!("{}", solution);
+    if let Err(err) = runner::run(&args) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
 }
@@ -0,0 +1,197 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+pub fn lcm(a: u64, b: u64) -> u64 {
+    (a * b) / gcd(a, b)
+}
+
+fn gcd_i128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+
+    pub fn new(num: i128, den: i128) -> Self {
+        Self::checked_new(num, den).expect("rational with a zero denominator")
+    }
+
+    pub fn checked_new(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let divisor = gcd_i128(num, den) * den.signum();
+        Some(Self {
+            num: num / divisor,
+            den: den / divisor,
+        })
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        if self.is_integer() {
+            Some(self.num)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    pub fn signum(&self) -> i128 {
+        self.num.signum()
+    }
+
+    // Cross-reducing first keeps the intermediate products as small as possible.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let a = gcd_i128(self.num, other.den).max(1);
+        let b = gcd_i128(other.num, self.den).max(1);
+        let num = (self.num / a).checked_mul(other.num / b)?;
+        let den = (self.den / b).checked_mul(other.den / a)?;
+        Self::checked_new(num, den)
+    }
+
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.num == 0 {
+            return None;
+        }
+        self.checked_mul(Self::checked_new(other.den, other.num)?)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let divisor = gcd_i128(self.den, other.den);
+        let left = self.num.checked_mul(other.den / divisor)?;
+        let right = other.num.checked_mul(self.den / divisor)?;
+        Self::checked_new(left.checked_add(right)?, (self.den / divisor).checked_mul(other.den)?)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(Self {
+            num: other.num.checked_neg()?,
+            den: other.den,
+        })
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self { num: value, den: 1 }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self::from(value as i128)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Whole parts first, so only the remainders (smaller than the denominators) get multiplied.
+        let (whole, other_whole) = (self.num.div_euclid(self.den), other.num.div_euclid(other.den));
+        if whole != other_whole {
+            return whole.cmp(&other_whole);
+        }
+        let left = self.num.rem_euclid(self.den).checked_mul(other.den);
+        let right = other.num.rem_euclid(other.den).checked_mul(self.den);
+        match (left, right) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            _ => panic!("rational comparison overflowed i128"),
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).expect("rational addition overflowed i128")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other).expect("rational subtraction overflowed i128")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other).expect("rational multiplication overflowed i128")
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Self) -> Self {
+        self.checked_div(other).expect("rational division by zero or overflow")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Self {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io {
+        file: String,
+        source: io::Error,
+    },
+    Syntax {
+        file: String,
+        line: usize,
+        column: usize,
+        text: String,
+        expected: String,
+        source_line: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io { file, source } => write!(f, "error: cannot read {}: {}", file, source),
+            ParseError::Syntax {
+                file,
+                line,
+                column,
+                text,
+                expected,
+                source_line,
+            } => {
+                if text.is_empty() {
+                    writeln!(f, "error: expected {}, found end of line", expected)?;
+                } else {
+                    writeln!(f, "error: expected {}, found `{}`", expected, text)?;
+                }
+                let gutter = " ".repeat(line.to_string().len());
+                writeln!(f, "{}--> {}:{}:{}", gutter, file, line, column)?;
+                writeln!(f, "{} |", gutter)?;
+                writeln!(f, "{} | {}", line, source_line)?;
+                write!(
+                    f,
+                    "{} | {}{}",
+                    gutter,
+                    " ".repeat(column - 1),
+                    "^".repeat(text.chars().count().max(1))
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Name used for inputs that do not come from a file, until `with_file` says otherwise.
+pub const UNNAMED_INPUT: &str = "<input>";
+
+impl ParseError {
+    pub fn with_file(self, input_file: &str) -> Self {
+        match self {
+            ParseError::Io { source, .. } => ParseError::Io {
+                file: input_file.to_string(),
+                source,
+            },
+            ParseError::Syntax {
+                line,
+                column,
+                text,
+                expected,
+                source_line,
+                ..
+            } => ParseError::Syntax {
+                file: input_file.to_string(),
+                line,
+                column,
+                text,
+                expected,
+                source_line,
+            },
+        }
+    }
+}
+
+// One line of the input, used to point errors at the offending token.
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, token: &str, expected: &str) -> ParseError {
+        ParseError::Syntax {
+            file: UNNAMED_INPUT.to_string(),
+            line: self.number,
+            column: self.column_of(token),
+            text: token.to_string(),
+            expected: expected.to_string(),
+            source_line: self.text.to_string(),
+        }
+    }
+
+    // Tokens are slices of `text`, so their column follows from the pointer offset.
+    // Anything else (e.g. a missing token) is reported at the end of the line.
+    fn column_of(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        let offset = if token_start >= start && token_start + token.len() <= start + self.text.len() {
+            token_start - start
+        } else {
+            self.text.len()
+        };
+
+        self.text[..offset].chars().count() + 1
+    }
+
+    pub fn number<T: FromStr>(&self, token: &str) -> Result<T, ParseError> {
+        token.trim().parse::<T>().map_err(|_| self.error(token.trim(), "number"))
+    }
+
+    pub fn field<'b>(&self, parts: &[&'b str], index: usize, expected: &str) -> Result<&'b str, ParseError> {
+        parts.get(index).copied().ok_or_else(|| self.error("", expected))
+    }
+}
+
+// The value of an ASCII digit, or with `full_width` also of `０` to `９`.
+pub fn digit_value(c: char, full_width: bool) -> Option<u8> {
+    match c {
+        '0'..='9' => Some(c as u8 - b'0'),
+        '０'..='９' if full_width => Some((c as u32 - '０' as u32) as u8),
+        _ => None,
+    }
+}
+
+pub fn read_lines(reader: impl BufRead) -> Result<Vec<String>, ParseError> {
+    reader
+        .lines()
+        .collect::<Result<Vec<String>, io::Error>>()
+        .map_err(|source| ParseError::Io {
+            file: UNNAMED_INPUT.to_string(),
+            source,
+        })
+}
+
+pub fn read_file<T>(
+    input_file: &str,
+    from_reader: impl FnOnce(BufReader<File>) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let file = File::open(input_file).map_err(|source| ParseError::Io {
+        file: input_file.to_string(),
+        source,
+    })?;
+
+    from_reader(BufReader::new(file)).map_err(|err| err.with_file(input_file))
+}
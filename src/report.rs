@@ -0,0 +1,165 @@
+use crate::answers::Verdict;
+use crate::runner::PartResult;
+use crate::solution::Failure;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format `{}`, expected json, csv or table", value)),
+        }
+    }
+}
+
+pub fn render(results: &[PartResult], format: Format) -> String {
+    match format {
+        Format::Table => render_table(results),
+        Format::Json => render_json(results),
+        Format::Csv => render_csv(results),
+    }
+}
+
+fn part_name(result: &PartResult) -> String {
+    match result.variant {
+        Some(variant) => format!("{} ({})", result.part, variant),
+        None => result.part.to_string(),
+    }
+}
+
+fn status(result: &PartResult) -> (&'static str, &str) {
+    match (&result.failure, &result.verdict) {
+        (Some(Failure::Failed(reason)), _) => ("FAILED", reason),
+        (Some(Failure::Cancelled(reason)), _) => ("CANCELLED", reason),
+        (None, Verdict::Match) => ("MATCH", ""),
+        (None, Verdict::Mismatch(reason)) => ("MISMATCH", reason),
+        (None, Verdict::Unknown) => ("UNKNOWN", ""),
+    }
+}
+
+fn render_table(results: &[PartResult]) -> String {
+    let header = ["day", "part", "answer", "status", "parse", "time", "input", "hash"];
+    let mut rows: Vec<Vec<String>> = vec![header.iter().map(|x| x.to_string()).collect()];
+    for result in results {
+        let (answer, status) = match (result.answer, &result.failure) {
+            (Some(answer), _) => (answer.to_string(), result.verdict.to_string()),
+            (None, Some(failure)) => ("-".to_string(), failure.to_string()),
+            (None, None) => ("-".to_string(), "not implemented".to_string()),
+        };
+        rows.push(vec![
+            result.day.to_string(),
+            part_name(result),
+            answer,
+            status,
+            format!("{:.2?}", result.parse_time),
+            format!("{:.2?}", result.time),
+            result.input.clone(),
+            result.input_hash.clone(),
+        ]);
+    }
+
+    let mut widths = vec![0; header.len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        out += cells.join("  ").trim_end();
+        out.push('\n');
+    }
+    out
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn render_json(results: &[PartResult]) -> String {
+    let objects: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let (status, detail) = status(result);
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"variant\": {}, \"answer\": {}, \"status\": {}, \"detail\": {}, \"parse_ns\": {}, \"time_ns\": {}, \"input\": {}, \"input_hash\": {}}}",
+                result.day,
+                result.part,
+                result.variant.map_or("null".to_string(), json_string),
+                result.answer.map_or("null".to_string(), |answer| answer.to_string()),
+                json_string(status),
+                json_string(detail),
+                result.parse_time.as_nanos(),
+                result.time.as_nanos(),
+                json_string(&result.input),
+                json_string(&result.input_hash)
+            )
+        })
+        .collect();
+
+    if objects.is_empty() {
+        return "[]\n".to_string();
+    }
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_csv(results: &[PartResult]) -> String {
+    let mut out = String::from("day,part,variant,answer,status,detail,parse_ns,time_ns,input,input_hash\n");
+    for result in results {
+        let (status, detail) = status(result);
+        let fields = [
+            result.day.to_string(),
+            result.part.to_string(),
+            result.variant.unwrap_or("").to_string(),
+            result.answer.map_or(String::new(), |answer| answer.to_string()),
+            status.to_string(),
+            detail.to_string(),
+            result.parse_time.as_nanos().to_string(),
+            result.time.as_nanos().to_string(),
+            result.input.clone(),
+            result.input_hash.clone(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        out += &fields.join(",");
+        out.push('\n');
+    }
+    out
+}
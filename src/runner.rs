@@ -0,0 +1,553 @@
+use crate::answers::{self, input_hash, AnswerStore, Mark, Verdict};
+use crate::bench::{self, measure, time, BenchResult};
+use crate::inputs::{self, Fetcher, InputManager, LocalFetcher, SessionFetcher};
+use crate::parse::ParseError;
+use crate::report::{self, Format};
+use crate::scaffold;
+use crate::solution::{Answer, Effect, Failure, Solution};
+use crate::{day1, day10, day2, day24, day3, day4, day5, day6, day7, day8, day9};
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+pub const USAGE: &str = "usage: advent-of-code-2023 <day> [--part 1|2|both] [--input PATH|-] [OPTIONS]
+       advent-of-code-2023 --all [--part 1|2|both] [OPTIONS]
+       advent-of-code-2023 new <day>
+
+options:
+  --cache-dir DIR               where puzzle inputs are cached (default inputs)
+  --session-file PATH           file with the adventofcode.com session token (default .session,
+                                AOC_SESSION takes precedence); without a token the inputs
+                                bundled in src are used
+  --opt KEY=VALUE               setting for a single day, e.g. `--opt area=7..27` for day 24
+  --format table|json|csv       how results are printed (default table)
+  --report                      also print what a day reports besides its answers, e.g. day 5's composed map
+  --variants                    also run alternative implementations, e.g. brute forces
+  --bench RUNS                  time parsing and every part RUNS times instead of printing answers
+  --bench-file PATH             where benchmark results are kept (default benchmarks.csv)
+
+answer options (marks need a single day and --part 1|2):
+  --answers PATH                answer store to check against (default answers.toml)
+  --accept[=VALUE]              record the answer (or VALUE) as accepted
+  --reject[=VALUE]              record the answer (or VALUE) as rejected
+  --too-low[=VALUE]             record the answer (or VALUE) as too low
+  --too-high[=VALUE]            record the answer (or VALUE) as too high";
+
+pub type Loader = fn(&mut dyn BufRead) -> Result<Box<dyn Solution>, ParseError>;
+
+pub struct Entry {
+    pub day: u32,
+    pub load: Loader,
+}
+
+fn load<S: Solution + 'static>(reader: &mut dyn BufRead) -> Result<Box<dyn Solution>, ParseError> {
+    Ok(Box::new(S::from_reader(reader)?))
+}
+
+pub const REGISTRY: &[Entry] = &[
+    Entry { day: 1, load: load::<day1::solution::Day> },
+    Entry { day: 2, load: load::<day2::solution::Day> },
+    Entry { day: 3, load: load::<day3::solution::Day> },
+    Entry { day: 4, load: load::<day4::solution::Day> },
+    Entry { day: 5, load: load::<day5::solution::Day> },
+    Entry { day: 6, load: load::<day6::solution::Day> },
+    Entry { day: 7, load: load::<day7::solution::Day> },
+    Entry { day: 8, load: load::<day8::solution::Day> },
+    Entry { day: 9, load: load::<day9::solution::Day> },
+    Entry { day: 10, load: load::<day10::solution::Day> },
+    Entry { day: 24, load: load::<day24::solution::Day> },
+];
+
+pub fn find(day: u32) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.day == day)
+}
+
+// `-` reads the puzzle input from stdin instead of a file.
+pub fn read_input(input: &str) -> Result<Vec<u8>, ParseError> {
+    let result = if input == "-" {
+        let mut bytes = Vec::new();
+        io::stdin().lock().read_to_end(&mut bytes).map(|_| bytes)
+    } else {
+        fs::read(input)
+    };
+
+    result.map_err(|source| ParseError::Io {
+        file: input_name(input).to_string(),
+        source,
+    })
+}
+
+pub fn load_input(entry: &Entry, input: &str, bytes: &[u8]) -> Result<Box<dyn Solution>, ParseError> {
+    (entry.load)(&mut &bytes[..]).map_err(|err| err.with_file(input_name(input)))
+}
+
+// Whether any of the settings changes the answers. Settings that only change the report
+// are refused unless the report is printed.
+pub fn configure(solution: &mut dyn Solution, options: &[(String, String)], report: bool) -> Result<bool, String> {
+    let mut changes_answers = false;
+    for (key, value) in options {
+        let effect = solution
+            .configure(key, value)
+            .map_err(|err| format!("--opt {}={}: {}", key, value, err))?;
+        if effect == Effect::ChangesReport && !report {
+            return Err(format!("--opt {}={} only changes the report, so it needs --report", key, value));
+        }
+        changes_answers |= effect == Effect::ChangesAnswers;
+    }
+    Ok(changes_answers)
+}
+
+fn input_name(input: &str) -> &str {
+    if input == "-" {
+        "<stdin>"
+    } else {
+        input
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    fn includes(&self, part: u8) -> bool {
+        match self {
+            Part::One => part == 1,
+            Part::Two => part == 2,
+            Part::Both => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Day(u32),
+    All,
+    // Generates the skeleton of a new day instead of running anything.
+    New(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub target: Target,
+    pub part: Part,
+    pub input: Option<String>,
+    pub options: Vec<(String, String)>,
+    pub cache_dir: String,
+    pub session_file: String,
+    pub answers: String,
+    pub marks: Vec<(Mark, Option<i128>)>,
+    pub variants: bool,
+    pub report: bool,
+    pub bench: Option<usize>,
+    pub bench_file: String,
+    pub format: Format,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut target: Option<Target> = None;
+        let mut part = Part::Both;
+        let mut input: Option<String> = None;
+        let mut options = Vec::new();
+        let mut cache_dir = inputs::DEFAULT_CACHE_DIR.to_string();
+        let mut session_file = inputs::DEFAULT_SESSION_FILE.to_string();
+        let mut answers = answers::DEFAULT_PATH.to_string();
+        let mut marks = Vec::new();
+        let mut variants = false;
+        let mut report = false;
+        let mut bench: Option<usize> = None;
+        let mut bench_file = bench::DEFAULT_PATH.to_string();
+        let mut format = Format::Table;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value)),
+                _ => (arg.as_str(), None),
+            };
+            let mark = match name {
+                "--accept" => Some(Mark::Accepted),
+                "--reject" => Some(Mark::Rejected),
+                "--too-low" => Some(Mark::TooLow),
+                "--too-high" => Some(Mark::TooHigh),
+                _ => None,
+            };
+            if let Some(mark) = mark {
+                let value = match value {
+                    Some(value) => Some(value.parse::<i128>().map_err(|_| format!("`{}` is not a number", value))?),
+                    None => None,
+                };
+                marks.push((mark, value));
+                continue;
+            }
+
+            match arg.as_str() {
+                "--all" => target = Some(Target::All),
+                "new" if target.is_none() => {
+                    let value = args.next().ok_or("new needs a day number")?;
+                    let day = value
+                        .parse::<u32>()
+                        .map_err(|_| format!("`{}` is not a day number", value))?;
+                    target = Some(Target::New(day));
+                }
+                "--part" | "-p" => {
+                    let value = args.next().ok_or("--part needs a value")?;
+                    part = match value.as_str() {
+                        "1" => Part::One,
+                        "2" => Part::Two,
+                        "both" => Part::Both,
+                        _ => return Err(format!("unknown part `{}`", value)),
+                    };
+                }
+                "--input" | "-i" => {
+                    input = Some(args.next().ok_or("--input needs a path")?);
+                }
+                "--opt" | "-o" => {
+                    let value = args.next().ok_or("--opt needs a KEY=VALUE setting")?;
+                    let (key, value) = value
+                        .split_once('=')
+                        .ok_or(format!("`{}` is not a KEY=VALUE setting", value))?;
+                    options.push((key.to_string(), value.to_string()));
+                }
+                "--cache-dir" => {
+                    cache_dir = args.next().ok_or("--cache-dir needs a path")?;
+                }
+                "--session-file" => {
+                    session_file = args.next().ok_or("--session-file needs a path")?;
+                }
+                "--answers" => {
+                    answers = args.next().ok_or("--answers needs a path")?;
+                }
+                "--format" | "-f" => {
+                    format = args.next().ok_or("--format needs json, csv or table")?.parse::<Format>()?;
+                }
+                "--variants" => variants = true,
+                "--report" => report = true,
+                "--bench" => {
+                    let value = args.next().ok_or("--bench needs a number of runs")?;
+                    let runs = value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|&runs| runs > 0)
+                        .ok_or(format!("`{}` is not a positive number of runs", value))?;
+                    bench = Some(runs);
+                }
+                "--bench-file" => {
+                    bench_file = args.next().ok_or("--bench-file needs a path")?;
+                }
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+                _ => {
+                    let day = arg
+                        .parse::<u32>()
+                        .map_err(|_| format!("`{}` is not a day number", arg))?;
+                    target = Some(Target::Day(day));
+                }
+            }
+        }
+
+        let target = target.ok_or("a day number or --all is required")?;
+        if target == Target::All && input.is_some() {
+            return Err("--input cannot be combined with --all".to_string());
+        }
+        // A mark is one submission, so it has to name the day and part it was submitted for.
+        if !marks.is_empty() && !matches!(target, Target::Day(_)) {
+            return Err("answers can only be marked for a single day".to_string());
+        }
+        if !marks.is_empty() && part == Part::Both {
+            return Err("answers can only be marked for one part, given with --part 1 or --part 2".to_string());
+        }
+        if bench.is_some() && !marks.is_empty() {
+            return Err("answers cannot be marked while benchmarking".to_string());
+        }
+        // Settings are day specific, so they cannot be handed to every day at once.
+        if !options.is_empty() && !matches!(target, Target::Day(_)) {
+            return Err("--opt settings need a single day".to_string());
+        }
+
+        Ok(Self {
+            target,
+            part,
+            input,
+            options,
+            cache_dir,
+            session_file,
+            answers,
+            marks,
+            variants,
+            report,
+            bench,
+            bench_file,
+            format,
+        })
+    }
+
+    pub fn input_manager(&self) -> Result<InputManager, String> {
+        let fetcher: Box<dyn Fetcher> = match SessionFetcher::from_env(&self.session_file)? {
+            Some(fetcher) => Box::new(fetcher),
+            None => Box::new(LocalFetcher::new("src")),
+        };
+        Ok(InputManager::new(&self.cache_dir, fetcher))
+    }
+
+    fn entries(&self) -> Result<Vec<(&'static Entry, String)>, String> {
+        let entries = match self.target {
+            Target::Day(day) => vec![find(day).ok_or(format!("day {} is not implemented", day))?],
+            Target::All => REGISTRY.iter().collect(),
+            Target::New(day) => return Err(format!("day {} is generated, not run", day)),
+        };
+        if let Some(input) = &self.input {
+            return Ok(entries.into_iter().map(|entry| (entry, input.clone())).collect());
+        }
+
+        let manager = self.input_manager()?;
+        entries
+            .into_iter()
+            .map(|entry| {
+                let path = manager.resolve(entry.day)?;
+                Ok((entry, path.display().to_string()))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u8,
+    pub variant: Option<&'static str>,
+    pub answer: Option<Answer>,
+    // Set when the part is implemented but has no answer; `answer` is `None` then.
+    pub failure: Option<Failure>,
+    pub input: String,
+    pub input_hash: String,
+    pub verdict: Verdict,
+    pub parse_time: Duration,
+    pub time: Duration,
+}
+
+pub fn run(args: &Args) -> Result<(), String> {
+    if let Target::New(day) = args.target {
+        for path in scaffold::generate(".", day)? {
+            println!("wrote {}", path.display());
+        }
+        return Ok(());
+    }
+    if let Some(runs) = args.bench {
+        return run_bench(args, runs);
+    }
+
+    let mut store = AnswerStore::load(&args.answers)?;
+    let mut results = Vec::new();
+    let mut failed = 0;
+    for (entry, input) in args.entries()? {
+        match run_entry(entry, &input, args, &mut store) {
+            Ok(entry_results) => results.extend(entry_results),
+            Err(err) => {
+                eprintln!("day {}: {}", entry.day, err);
+                failed += 1;
+            }
+        }
+    }
+
+    print!("{}", report::render(&results, args.format));
+
+    if !args.marks.is_empty() {
+        store.save()?;
+    }
+    // A cancelled part stopped because it was asked to, so it does not count as failed.
+    let failed_parts = results
+        .iter()
+        .filter(|result| matches!(result.failure, Some(Failure::Failed(_))))
+        .count();
+    match (failed, failed_parts) {
+        (0, 0) => Ok(()),
+        (_, 0) => Err(format!("{} day(s) failed", failed)),
+        (0, _) => Err(format!("{} part(s) failed", failed_parts)),
+        _ => Err(format!("{} day(s) and {} part(s) failed", failed, failed_parts)),
+    }
+}
+
+pub fn run_entry(entry: &Entry, input: &str, args: &Args, store: &mut AnswerStore) -> Result<Vec<PartResult>, String> {
+    let bytes = read_input(input).map_err(|err| err.to_string())?;
+    let input_hash = input_hash(&bytes);
+    let (solution, parse_time) = time(|| load_input(entry, input, &bytes));
+    let mut solution = solution.map_err(|err| err.to_string())?;
+    let changes_answers = configure(solution.as_mut(), &args.options, args.report)?;
+    for warning in solution.warnings() {
+        eprintln!("day {}: warning: {}", entry.day, warning);
+    }
+    if changes_answers && !args.marks.is_empty() {
+        return Err("answers cannot be marked for a run with --opt settings that change them".to_string());
+    }
+    if args.report {
+        if let Some(report) = solution.report() {
+            let report = format!("day {} report:\n{}\n", entry.day, report);
+            // Keep json and csv output parseable by printing the report next to it on stderr.
+            match args.format {
+                Format::Table => print!("{}", report),
+                Format::Json | Format::Csv => eprint!("{}", report),
+            }
+        }
+    }
+
+    let mut results = Vec::new();
+    for part in [1, 2] {
+        if !args.part.includes(part) {
+            continue;
+        }
+
+        let (outcome, part_time) = time(|| {
+            catch(|| match part {
+                1 => Some(Ok(solution.part1())),
+                _ => solution.part2(),
+            })
+        });
+        let (answer, failure) = match outcome {
+            Some(Ok(answer)) => (Some(answer), None),
+            Some(Err(failure)) => (None, Some(failure)),
+            None => (None, None),
+        };
+
+        for (mark, value) in &args.marks {
+            let value = value
+                .or(answer.map(|answer| answer.value()))
+                .ok_or(format!("day {} part {} has no answer to mark", entry.day, part))?;
+            store.mark(entry.day, part, &input_hash, *mark, value);
+        }
+
+        results.push((part, None, answer, failure, part_time));
+    }
+
+    if args.variants {
+        for variant in solution.variants() {
+            if args.part.includes(variant.part) {
+                let (outcome, variant_time) = time(|| catch(|| Some((variant.run)())));
+                let (answer, failure) = match outcome {
+                    Some(Ok(answer)) => (Some(answer), None),
+                    Some(Err(failure)) => (None, Some(failure)),
+                    None => (None, None),
+                };
+                results.push((variant.part, Some(variant.name), answer, failure, variant_time));
+            }
+        }
+    }
+
+    Ok(results
+        .into_iter()
+        .map(|(part, variant, answer, failure, part_time)| PartResult {
+            day: entry.day,
+            part,
+            variant,
+            answer,
+            failure,
+            input: input.to_string(),
+            input_hash: input_hash.clone(),
+            // Recorded answers belong to the default settings of a day.
+            verdict: match answer {
+                Some(answer) if !changes_answers => store.check(entry.day, part, &input_hash, answer),
+                _ => Verdict::Unknown,
+            },
+            parse_time,
+            time: part_time,
+        })
+        .collect())
+}
+
+// A part that panics is a bug, but it fails only that part instead of the whole run.
+fn catch(run: impl FnOnce() -> Option<Result<Answer, Failure>>) -> Option<Result<Answer, Failure>> {
+    panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|payload| {
+        let message = match (payload.downcast_ref::<String>(), payload.downcast_ref::<&str>()) {
+            (Some(message), _) => message.clone(),
+            (_, Some(message)) => message.to_string(),
+            _ => "unknown reason".to_string(),
+        };
+        Some(Err(Failure::Failed(format!("panicked: {}", message))))
+    })
+}
+
+pub fn bench_entry(entry: &Entry, input: &str, args: &Args, runs: usize) -> Result<Vec<BenchResult>, String> {
+    let bytes = read_input(input).map_err(|err| err.to_string())?;
+    let mut solution = load_input(entry, input, &bytes).map_err(|err| err.to_string())?;
+    configure(solution.as_mut(), &args.options, args.report)?;
+
+    let mut results = vec![BenchResult {
+        day: entry.day,
+        name: "parse".to_string(),
+        stats: measure(runs, || load_input(entry, input, &bytes).is_ok()),
+    }];
+    // A part that is not implemented is left out, and one without an answer is reported instead.
+    let mut bench_part = |name: String, part: &dyn Fn() -> Option<Result<Answer, Failure>>| {
+        let mut outcome = None;
+        let stats = measure(runs, || outcome = catch(part));
+        match outcome {
+            Some(Ok(_)) => results.push(BenchResult { day: entry.day, name, stats }),
+            Some(Err(failure)) => eprintln!("day {} {}: {}, not benchmarked", entry.day, name, failure),
+            None => {}
+        }
+    };
+    if args.part.includes(1) {
+        bench_part("part1".to_string(), &|| Some(Ok(solution.part1())));
+    }
+    if args.part.includes(2) {
+        bench_part("part2".to_string(), &|| solution.part2());
+    }
+    if args.variants {
+        for variant in solution.variants() {
+            if args.part.includes(variant.part) {
+                bench_part(format!("part{}-{}", variant.part, variant.name), &|| Some((variant.run)()));
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+fn run_bench(args: &Args, runs: usize) -> Result<(), String> {
+    let previous = bench::load_previous(&args.bench_file)?;
+
+    let mut results = Vec::new();
+    let mut failed = 0;
+    for (entry, input) in args.entries()? {
+        let entry_results = match bench_entry(entry, &input, args, runs) {
+            Ok(entry_results) => entry_results,
+            Err(err) => {
+                eprintln!("day {}: {}", entry.day, err);
+                failed += 1;
+                continue;
+            }
+        };
+        for result in entry_results {
+            let before = previous.iter().find(|before| before.day == result.day && before.name == result.name);
+            let comparison = match before {
+                Some(before) if !before.stats.mean.is_zero() => {
+                    let change = (result.stats.mean.as_secs_f64() / before.stats.mean.as_secs_f64() - 1.0) * 100.0;
+                    format!("  (previous {:.2?}, {:+.1}%)", before.stats.mean, change)
+                }
+                _ => String::new(),
+            };
+            println!(
+                "day {:>2} {:<18} mean {:>10.2?}  median {:>10.2?}  stddev {:>10.2?}{}",
+                result.day, result.name, result.stats.mean, result.stats.median, result.stats.stddev, comparison
+            );
+            results.push(result);
+        }
+    }
+
+    // Keep the numbers of days that were not part of this run.
+    for before in previous {
+        if !results.iter().any(|result| result.day == before.day && result.name == before.name) {
+            results.push(before);
+        }
+    }
+    results.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    bench::save(&args.bench_file, &results)?;
+    if failed > 0 {
+        return Err(format!("{} day(s) failed", failed));
+    }
+    Ok(())
+}
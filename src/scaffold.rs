@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SOLUTION_TEMPLATE: &str = "use crate::parse::{read_file, read_lines, ParseError};
+use crate::solution::{Answer, Failure, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub struct Day {
+    lines: Vec<String>,
+}
+
+impl Day {
+    pub fn new(input_file: &str) -> Result<Day, ParseError> {
+        read_file(input_file, Self::from_reader)
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Day, ParseError> {
+        let mut lines: Vec<String> = Default::default();
+        for text in read_lines(reader)? {
+            if text.is_empty() {
+                continue;
+            }
+            lines.push(text);
+        }
+
+        Ok(Day { lines })
+    }
+
+    pub fn solve(&self) -> u64 {
+        self.lines.len() as u64
+    }
+
+    pub fn solve2(&self) -> u64 {
+        0
+    }
+}
+
+impl FromStr for Day {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Day::from_reader(input.as_bytes())
+    }
+}
+
+impl Solution for Day {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError> {
+        Day::from_reader(reader)
+    }
+
+    fn part1(&self) -> Answer {
+        self.solve().into()
+    }
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        Some(Ok(self.solve2().into()))
+    }
+}
+";
+
+const TEST_TEMPLATE: &str = "#[test]
+#[ignore = \"needs the example from the puzzle text\"]
+fn dayN_example() {
+    let day: dayN::solution::Day = parse(\"\");
+
+    assert_eq!(day.part1(), Answer::Unsigned(0));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(0))));
+}
+";
+
+// Creates `src/dayN` from the template and registers the day in the library, the runner and
+// the example tests. Returns the files that were created or changed.
+pub fn generate(root: impl AsRef<Path>, day: u32) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day {} is not between 1 and 25", day));
+    }
+    let root = root.as_ref();
+    let dir = root.join("src").join(format!("day{}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let lib = root.join("src").join("lib.rs");
+    let runner = root.join("src").join("runner.rs");
+    let examples = root.join("tests").join("examples.rs");
+    let updates = [
+        (lib.clone(), register_module(&read(&lib)?, day)?),
+        (runner.clone(), register_runner(&read(&runner)?, day)?),
+        (examples.clone(), register_example(&read(&examples)?, day)?),
+    ];
+
+    fs::create_dir_all(&dir).map_err(|err| format!("cannot create {}: {}", dir.display(), err))?;
+    let mut changed = Vec::new();
+    let files = [
+        (dir.join("mod.rs"), "pub mod solution;\n".to_string()),
+        (dir.join("solution.rs"), SOLUTION_TEMPLATE.to_string()),
+        (dir.join("input.txt"), String::new()),
+    ];
+    for (path, content) in files.into_iter().chain(updates) {
+        fs::write(&path, content).map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+        changed.push(path);
+    }
+
+    Ok(changed)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path.display(), err))
+}
+
+fn day_number(text: &str, prefix: &str, suffix: &str) -> Option<u32> {
+    text.trim().strip_prefix(prefix)?.strip_suffix(suffix)?.parse().ok()
+}
+
+// Inserts `line` after the last line for a smaller day, or before the first one for a larger day.
+fn insert_ordered(content: &str, day: u32, line: &str, day_of: impl Fn(&str) -> Option<u32>) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let days: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| day_of(line).map(|day| (index, day)))
+        .collect();
+    let position = match days.iter().rfind(|(_, other)| *other < day) {
+        Some((index, _)) => index + 1,
+        None => days.first()?.0,
+    };
+
+    let mut out: Vec<&str> = lines[..position].to_vec();
+    out.push(line);
+    out.extend(&lines[position..]);
+    Some(out.join("\n") + "\n")
+}
+
+// Adds `dayN` to a `use ...::{day1, day10, ...};` list, keeping it sorted like rustfmt does.
+fn add_to_use_list(content: &str, prefix: &str, day: u32) -> Option<String> {
+    let start = content.find(prefix)? + prefix.len();
+    let end = start + content[start..].find("};")?;
+    let mut names: Vec<String> = content[start..end].split(',').map(|x| x.trim().to_string()).collect();
+    names.retain(|name| !name.is_empty());
+    names.push(format!("day{}", day));
+    names.sort();
+
+    Some(format!("{}{}{}", &content[..start], names.join(", "), &content[end..]))
+}
+
+fn register_module(lib: &str, day: u32) -> Result<String, String> {
+    insert_ordered(lib, day, &format!("pub mod day{};", day), |line| day_number(line, "pub mod day", ";"))
+        .ok_or("cannot find the day modules in src/lib.rs".to_string())
+}
+
+fn register_runner(runner: &str, day: u32) -> Result<String, String> {
+    let error = || "cannot find the day registry in src/runner.rs".to_string();
+    let runner = add_to_use_list(runner, "use crate::{", day).ok_or_else(error)?;
+    let entry = format!("    Entry {{ day: {}, load: load::<day{}::solution::Day> }},", day, day);
+    insert_ordered(&runner, day, &entry, |line| day_number(line.split(',').next()?, "Entry { day: ", "")).ok_or_else(error)
+}
+
+fn register_example(examples: &str, day: u32) -> Result<String, String> {
+    let examples = add_to_use_list(examples, "use advent_of_code_2023::{", day)
+        .ok_or("cannot find the day imports in tests/examples.rs".to_string())?;
+    let test = TEST_TEMPLATE.replace("dayN", &format!("day{}", day));
+
+    // Tests are grouped by day, so the stub goes in front of the first test of a later day.
+    let mut position = examples.len();
+    let mut offset = 0;
+    for block in examples.split_inclusive("#[test]\n") {
+        let later = block
+            .lines()
+            .find(|line| !line.starts_with("#["))
+            .and_then(|line| line.strip_prefix("fn day"))
+            .and_then(|x| x.split('_').next())
+            .and_then(|x| x.parse::<u32>().ok())
+            .is_some_and(|other| other > day);
+        if later {
+            position = offset - "#[test]\n".len();
+            break;
+        }
+        offset += block.len();
+    }
+
+    if position == examples.len() {
+        Ok(format!("{}\n{}", examples, test))
+    } else {
+        Ok(format!("{}{}\n{}", &examples[..position], test, &examples[position..]))
+    }
+}
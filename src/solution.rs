@@ -0,0 +1,116 @@
+use crate::parse::{read_file, ParseError};
+use std::fmt;
+use std::io::BufRead;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Signed(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl Answer {
+    pub fn value(&self) -> i128 {
+        match self {
+            Answer::Unsigned(value) => *value as i128,
+            Answer::Signed(value) => *value as i128,
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+// Why an implemented part has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    // The part found no answer in this input, e.g. no rock within the searched speeds.
+    Failed(String),
+    // Stopped on request before it finished, e.g. by `--opt timeout=SECONDS`.
+    Cancelled(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Failed(reason) => write!(f, "FAILED ({})", reason),
+            Failure::Cancelled(reason) => write!(f, "CANCELLED ({})", reason),
+        }
+    }
+}
+
+// An alternative implementation of one part, e.g. a brute force kept to cross-check the fast one.
+pub struct Variant<'a> {
+    pub part: u8,
+    pub name: &'static str,
+    pub run: Box<dyn Fn() -> Result<Answer, Failure> + 'a>,
+}
+
+// What a `--opt` setting does to the answers of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    // The puzzle itself changes, so recorded answers no longer apply.
+    ChangesAnswers,
+    // Only how the answers are found changes, e.g. the number of threads.
+    KeepsAnswers,
+    // Only what `--report` prints changes, so the setting is useless without it.
+    ChangesReport,
+}
+
+// Every day implements this so it can be run without knowing its concrete type.
+// `part2` stays `None` until the second half of the puzzle is solved, and is `Some(Err(..))`
+// when it is solved but finds no answer in the given input.
+pub trait Solution {
+    fn from_reader(reader: impl BufRead) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+
+    fn parse(input_file: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized,
+    {
+        read_file(input_file, Self::from_reader)
+    }
+
+    fn part1(&self) -> Answer;
+
+    fn part2(&self) -> Option<Result<Answer, Failure>> {
+        None
+    }
+
+    fn variants(&self) -> Vec<Variant<'_>> {
+        Vec::new()
+    }
+
+    // Extra output for `--report`, e.g. intermediate results worth checking by hand.
+    fn report(&self) -> Option<String> {
+        None
+    }
+
+    // Things about the input worth knowing before trusting the answers, printed once on stderr.
+    fn warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    // Day specific settings, given as `--opt key=value` on the command line.
+    fn configure(&mut self, key: &str, _value: &str) -> Result<Effect, String> {
+        Err(format!("unknown option `{}`", key))
+    }
+}
@@ -0,0 +1,65 @@
+use advent_of_code_2023::aho_corasick::{AhoCorasick, Match};
+
+fn brute_force(patterns: &[&str], text: &str) -> Vec<Match> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut matches = Vec::new();
+    for end in 1..=chars.len() {
+        for (pattern, word) in patterns.iter().enumerate() {
+            let word: Vec<char> = word.chars().collect();
+            if !word.is_empty() && word.len() <= end && chars[end - word.len()..end] == word[..] {
+                matches.push(Match { pattern, start: end - word.len(), end });
+            }
+        }
+    }
+    matches
+}
+
+#[test]
+fn finds_overlapping_matches() {
+    let patterns = ["he", "she", "his", "hers"];
+    let automaton = AhoCorasick::new(&patterns);
+    let mut found: Vec<Match> = automaton.find_iter("ushers".chars()).collect();
+    found.sort_by_key(|m| (m.end, m.pattern));
+
+    assert_eq!(
+        found,
+        [
+            Match { pattern: 0, start: 2, end: 4 },
+            Match { pattern: 1, start: 1, end: 4 },
+            Match { pattern: 3, start: 2, end: 6 },
+        ]
+    );
+}
+
+#[test]
+fn agrees_with_brute_force() {
+    let patterns = ["one", "two", "eight", "nine", "e", "ei", "ne", "fünf", "ünf"];
+    let automaton = AhoCorasick::new(&patterns);
+    for text in ["eightwone", "nineeightninefünfünf", "", "xyz", "oneoneone", "twone8"] {
+        let mut found: Vec<Match> = automaton.find_iter(text.chars()).collect();
+        found.sort_by_key(|m| (m.end, m.pattern));
+        let mut expected = brute_force(&patterns, text);
+        expected.sort_by_key(|m| (m.end, m.pattern));
+        assert_eq!(found, expected, "{}", text);
+    }
+}
+
+#[test]
+fn leftmost_longest_match() {
+    // "bc" ends first, but "abcd" starts earlier.
+    let automaton = AhoCorasick::new(&["bc", "abcd", "a", "ab"]);
+    assert_eq!(automaton.leftmost_longest("xabcd".chars()), Some(Match { pattern: 1, start: 1, end: 5 }));
+    assert_eq!(automaton.leftmost_longest("xabce".chars()), Some(Match { pattern: 3, start: 1, end: 3 }));
+    assert_eq!(automaton.leftmost_longest("xyz".chars()), None);
+
+    // Reading stops once nothing can start earlier, so endless input is fine.
+    let automaton = AhoCorasick::new(&["one", "two"]);
+    let text = "xxtwo".chars().chain(std::iter::repeat('x'));
+    assert_eq!(automaton.leftmost_longest(text), Some(Match { pattern: 1, start: 2, end: 5 }));
+}
+
+#[test]
+fn ignores_empty_and_repeated_patterns() {
+    let automaton = AhoCorasick::new(&["", "ab", "ab"]);
+    assert_eq!(automaton.find_iter("abab".chars()).map(|m| m.pattern).collect::<Vec<_>>(), [1, 1]);
+}
@@ -0,0 +1,76 @@
+use advent_of_code_2023::answers::{input_hash, AnswerStore, Mark, Record, Verdict};
+use advent_of_code_2023::solution::Answer;
+use std::env;
+use std::fs;
+
+#[test]
+fn input_hash_is_stable() {
+    assert_eq!(input_hash(b""), "cbf29ce484222325");
+    assert_eq!(input_hash(b"a"), "af63dc4c8601ec8c");
+}
+
+#[test]
+fn record_checks_accepted_answer() {
+    let mut record = Record::default();
+    record.mark(Mark::Accepted, 42);
+
+    assert_eq!(record.check(42), Verdict::Match);
+    assert_eq!(record.check(41), Verdict::Mismatch("accepted answer is 42".to_string()));
+}
+
+#[test]
+fn record_checks_rejected_bounds() {
+    let mut record = Record::default();
+    record.mark(Mark::TooLow, 13135);
+    record.mark(Mark::TooHigh, 26270);
+    record.mark(Mark::Rejected, 20000);
+
+    assert_eq!(record.check(13135), Verdict::Mismatch("13135 was too low".to_string()));
+    assert_eq!(record.check(30000), Verdict::Mismatch("26270 was too high".to_string()));
+    assert_eq!(record.check(20000), Verdict::Mismatch("rejected before".to_string()));
+    assert_eq!(record.check(20001), Verdict::Unknown);
+}
+
+#[test]
+fn store_round_trips_through_toml() {
+    let path = env::temp_dir().join(format!("aoc-answers-{}.toml", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut store = AnswerStore::load(&path).expect("missing file is an empty store");
+    assert_eq!(store.check(24, 1, "abc", Answer::Unsigned(1)), Verdict::Unknown);
+
+    store.mark(24, 1, "abc", Mark::TooLow, 13135);
+    store.mark(24, 1, "abc", Mark::TooHigh, 26270);
+    store.mark(24, 1, "abc", Mark::Rejected, 20000);
+    store.mark(1, 2, "def", Mark::Accepted, 54076);
+    store.save().expect("store should be writable");
+
+    let reloaded = AnswerStore::load(&path).expect("saved store should parse");
+    fs::remove_file(&path).expect("saved store should exist");
+
+    assert_eq!(reloaded.to_toml(), store.to_toml());
+    assert_eq!(reloaded.check(1, 2, "def", Answer::Unsigned(54076)), Verdict::Match);
+    assert_eq!(
+        reloaded.check(24, 1, "abc", Answer::Unsigned(13000)),
+        Verdict::Mismatch("13135 was too low".to_string())
+    );
+    assert_eq!(reloaded.check(24, 1, "other", Answer::Unsigned(13000)), Verdict::Unknown);
+}
+
+#[test]
+fn known_wrong_answers_hold_for_any_input() {
+    let path = env::temp_dir().join(format!("aoc-answers-known-{}.toml", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let mut store = AnswerStore::load(&path).expect("missing file is an empty store");
+
+    let rejected = Verdict::Mismatch("rejected before".to_string());
+    assert_eq!(store.check(24, 1, "0123456789abcdef", Answer::Unsigned(13135)), rejected);
+    assert_eq!(store.check(24, 1, "fedcba9876543210", Answer::Unsigned(26270)), rejected);
+    assert_eq!(store.check(24, 1, "0123456789abcdef", Answer::Unsigned(20000)), Verdict::Unknown);
+    assert_eq!(store.check(24, 2, "0123456789abcdef", Answer::Unsigned(13135)), Verdict::Unknown);
+
+    // They are not written to the file, and an accepted answer for the input still wins.
+    assert!(!store.to_toml().contains("day24"));
+    store.mark(24, 1, "0123456789abcdef", Mark::Accepted, 13135);
+    assert_eq!(store.check(24, 1, "0123456789abcdef", Answer::Unsigned(13135)), Verdict::Match);
+}
@@ -0,0 +1,31 @@
+use advent_of_code_2023::bench::{load_previous, save, BenchResult, Stats};
+use std::env;
+use std::fs;
+use std::time::Duration;
+
+#[test]
+fn stats_from_samples() {
+    let samples: Vec<Duration> = [4, 1, 3, 2].iter().map(|&x| Duration::from_millis(x)).collect();
+    let stats = Stats::from_samples(&samples);
+
+    assert_eq!(stats.runs, 4);
+    assert_eq!(stats.mean, Duration::from_micros(2500));
+    assert_eq!(stats.median, Duration::from_micros(2500));
+    assert_eq!(stats.stddev.as_micros(), 1118);
+}
+
+#[test]
+fn results_round_trip_through_csv() {
+    let path = env::temp_dir().join(format!("aoc-bench-{}.csv", std::process::id()));
+    let results = vec![BenchResult {
+        day: 5,
+        name: "part2-bruteforce".to_string(),
+        stats: Stats::from_samples(&[Duration::from_nanos(10), Duration::from_nanos(20), Duration::from_nanos(60)]),
+    }];
+
+    save(&path, &results).expect("results should be writable");
+    let previous = load_previous(&path).expect("saved results should parse");
+    fs::remove_file(&path).expect("saved results should exist");
+
+    assert_eq!(previous, results);
+}
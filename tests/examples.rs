@@ -0,0 +1,681 @@
+use advent_of_code_2023::parse::ParseError;
+use advent_of_code_2023::solution::{Answer, Solution};
+use advent_of_code_2023::{day1, day10, day2, day24, day3, day4, day5, day6, day7, day8, day9};
+use std::str::FromStr;
+
+fn parse<S: FromStr<Err = ParseError>>(input: &str) -> S {
+    match input.parse::<S>() {
+        Ok(day) => day,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+#[test]
+fn day1_example() {
+    let day: day1::solution::Day = parse("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n");
+
+    assert_eq!(day.part1(), Answer::Unsigned(142));
+}
+
+#[test]
+fn day1_example_spelled() {
+    let day: day1::solution::Day = parse(
+        "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n",
+    );
+
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(281))));
+}
+
+#[test]
+fn day1_overlapping_spelled_digits() {
+    let eightwo: day1::solution::Day = parse("eightwo\n");
+    let oneight: day1::solution::Day = parse("oneight\n");
+    let twone: day1::solution::Day = parse("3twone\n");
+
+    assert_eq!(eightwo.part2(), Some(Ok(Answer::Unsigned(82))));
+    assert_eq!(oneight.part2(), Some(Ok(Answer::Unsigned(18))));
+    assert_eq!(twone.part2(), Some(Ok(Answer::Unsigned(31))));
+}
+
+#[test]
+fn day1_single_digit_counts_twice() {
+    let day: day1::solution::Day = parse("treb7uchet\n");
+
+    assert_eq!(day.part1(), Answer::Unsigned(77));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(77))));
+}
+
+#[test]
+fn day1_lines_without_digits() {
+    let day: day1::solution::Day = parse("two1nine\n\nabc\nx7yfive\n");
+
+    assert_eq!(day.part1(), Answer::Unsigned(11 + 77));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(29 + 75))));
+
+    let calibrations = day.calibrations(2);
+    assert_eq!(calibrations.iter().map(|c| (c.line, c.value())).collect::<Vec<_>>(), [(1, Some(29)), (3, None), (4, Some(75))]);
+    let (first, last) = calibrations[2].digits.unwrap();
+    assert_eq!((first.value, first.start, first.spelled), (7, 1, false));
+    assert_eq!((last.value, last.start, last.end, last.spelled), (5, 3, 7, true));
+
+    let report = day.report().unwrap();
+    assert!(report.contains("line 3 `abc`: no digit\n"));
+    assert!(report.contains("line 4 `x7yfive`: 75 = first 7 at column 2 (symbol `7`), last 5 at column 4 (spelled `five`)\n"));
+    assert!(report.contains("1 of 3 non-blank lines have no digit"));
+    assert_eq!(
+        day.warnings(),
+        [
+            "part 1: line(s) 3 have no digit and are left out of the total",
+            "part 2: line(s) 3 have no digit and are left out of the total"
+        ]
+    );
+}
+
+#[test]
+fn day1_unicode_lines() {
+    let mut day: day1::solution::Day = parse("ñandú3x７\nfünf８€two\n");
+    assert_eq!(day.part1(), Answer::Unsigned(33));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(33 + 22))));
+
+    day.configure("full-width", "true").unwrap();
+    assert_eq!(day.part1(), Answer::Unsigned(37 + 88));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(37 + 82))));
+
+    // Columns count characters, not bytes.
+    let (first, last) = day.calibrations(2)[1].digits.unwrap();
+    assert_eq!((first.start, last.start, last.end), (4, 6, 9));
+    assert!(day.report().unwrap().contains("last 7 at column 8 (symbol `７`)"));
+}
+
+#[test]
+fn day1_long_lines() {
+    let line = format!("{}eight{}twone{}", "x".repeat(100_000), "y".repeat(100_000), "z".repeat(100_000));
+    let day: day1::solution::Day = parse(&format!("{}\n", line).repeat(4));
+
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(81 * 4))));
+}
+
+#[test]
+fn day1_other_vocabularies() {
+    let english: day1::solution::Day = parse("zeroneight\nvierfünf7\nsixVIII\n");
+    assert_eq!(english.solve2(), 18 + 77 + 66);
+
+    let mut day: day1::solution::Day = parse("zeroneight\nvierfünf7\nXIVabc\nsixVIII\n");
+    // "XIV" has no "XI", so it reads as IV twice.
+    day.configure("words", "english,zero,german,roman").unwrap();
+    assert_eq!(day.solve2(), 8 + 47 + 44 + 68);
+
+    let mut day: day1::solution::Day = parse("3IX\nVII\n");
+    day.configure("words", "roman").unwrap();
+    assert_eq!(day.solve2(), 39 + 77);
+    assert!(day.configure("words", "klingon").is_err_and(|err| err.contains("english, german")));
+}
+
+#[test]
+fn day1_vocabulary_from_file() {
+    let words = "# Dutch\neen=1\ntwee = 2\n\ndrie=3\n";
+    let vocabulary = day1::vocabulary::Vocabulary::from_reader(words.as_bytes()).unwrap();
+    assert_eq!(vocabulary.words().collect::<Vec<_>>(), [("een", 1), ("twee", 2), ("drie", 3)]);
+
+    let mut day: day1::solution::Day = parse("tweedrieeen\n");
+    day.set_vocabulary(vocabulary);
+    assert_eq!(day.solve2(), 21);
+
+    let err = day1::vocabulary::Vocabulary::from_reader("vier=10\n".as_bytes()).unwrap_err();
+    assert!(err.to_string().contains("expected a digit from 0 to 9, found `10`"));
+    assert!(day.configure("words-file", "no/such/file").is_err());
+}
+
+const DAY2: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+#[test]
+fn day2_example() {
+    let day: day2::solution::Day = parse(DAY2);
+
+    assert_eq!(day.part1(), Answer::Unsigned(8));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(2286))));
+}
+
+const DAY3: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+#[test]
+fn day3_example() {
+    let day: day3::solution::Day = parse(DAY3);
+
+    assert_eq!(day.part1(), Answer::Unsigned(4361));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(467835))));
+}
+
+#[test]
+fn day3_numbers_at_row_edges() {
+    let day: day3::solution::Day = parse("....12\n.*..*.\n3.....\n");
+
+    assert_eq!(day.part1(), Answer::Unsigned(15));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(0))));
+}
+
+#[test]
+fn day3_unicode_schematic() {
+    let symbols: day3::solution::Day = parse(&DAY3.replace('#', "§").replace('$', "€"));
+    assert_eq!(symbols.part1(), Answer::Unsigned(4361));
+
+    // Full-width digits are symbols unless the option says otherwise.
+    let full_width = DAY3.replace('7', "７").replace('3', "３");
+    let mut day: day3::solution::Day = parse(&full_width);
+    assert_ne!(day.part1(), Answer::Unsigned(4361));
+    day.configure("full-width", "true").unwrap();
+    assert_eq!(day.part1(), Answer::Unsigned(4361));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(467835))));
+    assert!(day.configure("full-width", "yes").is_err());
+}
+
+const DAY4: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+#[test]
+fn day4_example() {
+    let day: day4::solution::Day = parse(DAY4);
+
+    assert_eq!(day.part1(), Answer::Signed(13));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(30))));
+}
+
+const DAY5: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+#[test]
+fn day5_example() {
+    let day: day5::solution::Day = parse(DAY5);
+
+    assert_eq!(day.part1(), Answer::Signed(35));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(46))));
+    assert_eq!(day.solve2_bruteforce(), 46);
+}
+
+#[test]
+fn day5_example_seed_to_location() {
+    let day: day5::solution::Day = parse(DAY5);
+
+    assert_eq!([79, 14, 55, 13].map(|seed| day.location(seed)), [82, 43, 86, 35]);
+    assert_eq!(day.seed_to_location().apply_ranges(&[79..93, 55..68])[0].start, 46);
+
+    // The exported rows read back as a one-stage map that agrees everywhere.
+    let exported = day.seed_to_location().to_string();
+    let mut reread = advent_of_code_2023::interval::IntervalMap::new();
+    for row in exported.lines() {
+        let numbers: Vec<u64> = row.split(' ').map(|x| x.parse().unwrap()).collect();
+        reread.insert(numbers[0], numbers[1], numbers[2]).unwrap();
+    }
+    assert_eq!(&reread, day.seed_to_location());
+    assert!(day.report().unwrap().starts_with("seed-to-location map:\n"));
+}
+
+#[test]
+fn day5_bruteforce_on_threads() {
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+    // Ranges longer than a chunk, so the workers share them.
+    let day: day5::solution::Day = parse(&DAY5.replace("seeds: 79 14 55 13", "seeds: 79 3000000 55 13"));
+    let done = AtomicU64::new(0);
+    let progress = |seeds: u64, total: u64| {
+        assert_eq!(total, 3000013);
+        done.fetch_max(seeds, Ordering::Relaxed);
+    };
+    assert_eq!(day.solve2_bruteforce_with(4, progress, &AtomicBool::new(false)), Some(day.solve2() as u64));
+    assert_eq!(done.into_inner(), 3000013);
+
+    let cancel = AtomicBool::new(true);
+    assert_eq!(day.solve2_bruteforce_with(2, |_, _| {}, &cancel), None);
+}
+
+#[test]
+fn day5_example_reverse_lookup() {
+    let mut day: day5::solution::Day = parse(DAY5);
+
+    // The part 2 answer: location 46 comes only from seed 82, which is inside `79 14`.
+    let lookup = day.reverse_lookup(46..47);
+    assert_eq!(lookup.seeds, vec![82..83]);
+    assert_eq!(lookup.planted, vec![82..83]);
+    assert!(lookup.listed.is_empty());
+
+    let lookup = day.reverse_lookup(0..100);
+    for seed in 0..200 {
+        let hit = day.location(seed) < 100;
+        assert_eq!(lookup.seeds.iter().any(|range| range.contains(&seed)), hit, "seed {}", seed);
+    }
+    assert_eq!(lookup.listed, vec![79, 14, 55, 13]);
+    assert_eq!(lookup.planted, vec![55..68, 79..93]);
+
+    day.configure("reverse", "35").unwrap();
+    assert!(day.report().unwrap().contains("location 35..36 come from seed [13..14]"));
+    assert!(day.configure("reverse", "x..1").is_err());
+    assert!(day.configure("reverse", "18446744073709551615").is_err_and(|err| err.contains("below u64::MAX")));
+    assert!(day.configure("reverse", "50..40").is_err_and(|err| err.contains("empty range")));
+    assert!(day.configure("reverse", "40..40").is_err());
+}
+
+#[test]
+fn day5_example_lookups_between_categories() {
+    let day: day5::solution::Day = parse(DAY5);
+
+    assert_eq!(day.lookup("seed", "soil", 79), Some(81));
+    assert_eq!(day.lookup("soil", "water", 81), Some(81));
+    assert_eq!(day.lookup("seed", "location", 79), Some(82));
+    assert_eq!(day.lookup("location", "seed", 82), None);
+    assert_eq!(day.path("seed", "location").map(|path| path.len()), Some(7));
+    assert_eq!(day.map_between("seed", "fertilizer").map(|map| map.apply(14)), Ok(53));
+}
+
+#[test]
+fn day5_reordered_almanac_with_extra_stages() {
+    // Sections in any order, a shortcut next to the long way round and a stage that is never used.
+    let mut day: day5::solution::Day = parse(
+        "\
+seeds: 1 10
+
+water-to-location map:
+100 0 50
+
+seed-to-soil map:
+20 0 50
+
+soil-to-water map:
+0 20 5
+
+seed-to-water map:
+30 0 3
+
+soil-to-sunlight map:
+0 0 1
+",
+    );
+
+    // The direct `seed-to-water` map is the shorter path.
+    assert_eq!(day.path("seed", "location").map(|path| path.len()), Some(2));
+    assert_eq!(day.location(1), 131);
+    assert_eq!(day.part1(), Answer::Signed(110));
+    assert_eq!(day.lookup("seed", "sunlight", 1), Some(21));
+
+    day.configure("target", "soil").unwrap();
+    assert_eq!(day.part1(), Answer::Signed(21));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(21))));
+    assert!(day.configure("source", "sunlight").is_err());
+}
+
+#[test]
+fn day5_needs_a_chain_to_location() {
+    let day = "seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n".parse::<day5::solution::Day>();
+
+    assert!(day.is_err_and(|err| err.to_string().contains("a chain of maps from `seed` to `location`")));
+}
+
+#[test]
+fn day5_seed_ranges_are_checked_while_parsing() {
+    let almanac = |seeds: &str| format!("seeds: {}
+
+seed-to-location map:
+1 2 3
+", seeds).parse::<day5::solution::Day>();
+
+    assert!(almanac("1 2").is_ok());
+    assert!(almanac("1 2 3").is_err_and(|err| err.to_string().contains("a length after the last start")));
+    assert!(almanac("1 0").is_err_and(|err| err.to_string().contains("1:10")));
+    assert!(almanac("18446744073709551615 1").is_err_and(|err| err.to_string().contains("within u64")));
+}
+
+#[test]
+fn day6_example() {
+    let day: day6::solution::Day = parse("Time:      7  15   30\nDistance:  9  40  200\n");
+
+    assert_eq!(day.part1(), Answer::Signed(288));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(71503))));
+}
+
+#[test]
+fn day7_example() {
+    let day: day7::solution::Day = parse("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n");
+
+    assert_eq!(day.part1(), Answer::Unsigned(6440));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(5905))));
+}
+
+#[test]
+fn day7_jokers() {
+    let all_jokers: day7::solution::Day = parse("JJJJJ 1\n22223 10\n");
+    let two_pair_joker: day7::solution::Day = parse("2233J 1\n44456 10\n");
+
+    // JJJJJ is five of a kind but its jokers are the weakest cards.
+    assert_eq!(all_jokers.part2(), Some(Ok(Answer::Unsigned(12))));
+    // Two pair plus a joker becomes a full house and beats three of a kind.
+    assert_eq!(two_pair_joker.part2(), Some(Ok(Answer::Unsigned(12))));
+}
+
+#[test]
+fn day8_example() {
+    let day: day8::solution::Day = parse(
+        "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+",
+    );
+
+    assert_eq!(day.part1(), Answer::Unsigned(2));
+}
+
+#[test]
+fn day8_example_repeated_instructions() {
+    let day: day8::solution::Day = parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n");
+
+    assert_eq!(day.part1(), Answer::Unsigned(6));
+}
+
+#[test]
+fn day8_example_ghosts() {
+    let day: day8::solution::Day = parse(
+        "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+",
+    );
+
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(6))));
+    assert_eq!(day.solve2_brute_force(), 6);
+    // Far too slow for the real input, so it is not offered as a variant.
+    assert!(day.variants().is_empty());
+}
+
+#[test]
+fn day9_example() {
+    let day: day9::solution::Day = parse("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n");
+
+    assert_eq!(day.part1(), Answer::Signed(114));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(2))));
+}
+
+#[test]
+fn day9_negative_extrapolation() {
+    let day: day9::solution::Day = parse("1 3 5\n");
+
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(-1))));
+    assert_eq!(parse::<day9::solution::Day>("5 1 -3\n").part1(), Answer::Signed(-7));
+}
+
+#[test]
+fn day9_needs_two_numbers_per_history() {
+    let day = "0 3 6\n5\n".parse::<day9::solution::Day>();
+
+    assert!(day.is_err_and(|err| err.to_string().contains("a history of at least two numbers") && err.to_string().contains(":2:2")));
+}
+
+#[test]
+fn day10_example_simple_loop() {
+    let day: day10::solution::Day = parse(".....\n.S-7.\n.|.|.\n.L-J.\n.....\n");
+
+    assert_eq!(day.part1(), Answer::Unsigned(4));
+}
+
+#[test]
+fn day10_example_complex_loop() {
+    let day: day10::solution::Day = parse("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...\n");
+
+    assert_eq!(day.part1(), Answer::Unsigned(8));
+}
+
+#[test]
+fn day10_example_enclosed_tiles() {
+    let day: day10::solution::Day = parse(
+        "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+",
+    );
+
+    assert_eq!(day.start_shape(), 'F');
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(4))));
+    assert_eq!(day.solve2_shoelace(), 4);
+}
+
+#[test]
+fn day10_example_enclosed_tiles_larger() {
+    let day: day10::solution::Day = parse(
+        "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+",
+    );
+
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(8))));
+    assert_eq!(day.solve2_shoelace(), 8);
+}
+
+#[test]
+fn day10_example_enclosed_tiles_with_junk() {
+    let day: day10::solution::Day = parse(
+        "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+",
+    );
+
+    assert_eq!(day.start_shape(), '7');
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(10))));
+    assert_eq!(day.solve2_shoelace(), 10);
+}
+
+#[test]
+fn day10_start_with_three_connecting_neighbours() {
+    // The `|` above `S` points at it too, but only right and down close the loop.
+    let day: day10::solution::Day = parse(".|...\n.S-7.\n.|.|.\n.L-J.\n.....\n");
+
+    assert_eq!(day.start_shape(), 'F');
+    assert_eq!(day.part1(), Answer::Unsigned(4));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(1))));
+    assert_eq!(day.solve2_shoelace(), 1);
+}
+
+#[test]
+fn day10_start_off_the_loop() {
+    let err = ".....\n.S-7.\n.|.|.\n.L-..\n".parse::<day10::solution::Day>().err().unwrap().to_string();
+
+    assert!(err.contains("expected a starting tile on a closed loop of pipes, found `S`"), "{}", err);
+    assert!(err.contains("--> <input>:2:2"), "{}", err);
+}
+
+#[test]
+fn day24_example_intersections() {
+    use advent_of_code_2023::day24::solution::{Intersection, Point, Vector};
+    use advent_of_code_2023::math::Rational;
+
+    let day: day24::solution::Day = parse("19, 13, 30 @ -2, 1, -2\n");
+    let vector = |pos: [i128; 2], vel: [i128; 2]| Vector {
+        pos: Point { x: pos[0].into(), y: pos[1].into(), z: Rational::ZERO },
+        vel: Point { x: vel[0].into(), y: vel[1].into(), z: Rational::ZERO },
+    };
+
+    assert_eq!(
+        day.intersect(&vector([19, 13], [-2, 1]), &vector([18, 19], [-1, -1])),
+        Intersection::Intersects(Point { x: Rational::new(43, 3), y: Rational::new(46, 3), z: Rational::ZERO })
+    );
+    assert_eq!(day.intersect(&vector([18, 19], [-1, -1]), &vector([20, 25], [-2, -2])), Intersection::Parallel);
+    assert_eq!(day.intersect(&vector([18, 19], [-1, -1]), &vector([20, 21], [2, 2])), Intersection::Coincident);
+}
+
+#[test]
+fn day24_example_test_area() {
+    use advent_of_code_2023::day24::solution::Plane;
+
+    let input = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+    let day = parse::<day24::solution::Day>(input).with_area(7, 27);
+    assert_eq!(day.part1(), Answer::Unsigned(2));
+    assert_eq!(day.with_plane(Plane::XZ).part1(), Answer::Unsigned(0));
+
+    let mut day: day24::solution::Day = parse(input);
+    assert_eq!(day.part1(), Answer::Unsigned(0));
+    day.configure("area", "7..27").unwrap();
+    assert_eq!(day.part1(), Answer::Unsigned(2));
+    assert!(day.configure("area", "27..7").is_err());
+    assert!(day.configure("plane", "xw").is_err());
+    assert!(day.configure("speed", "1").is_err());
+}
+
+#[test]
+fn day24_crossings_in_3d() {
+    use advent_of_code_2023::day24::solution::Plane;
+
+    let meeting: day24::solution::Day = parse("0, 0, 0 @ 1, 1, 1\n10, 0, 0 @ -1, 1, 1\n");
+    let meeting = meeting.with_area(0, 10).with_plane(Plane::XYZ);
+    assert_eq!(meeting.part1(), Answer::Unsigned(1));
+
+    let skew: day24::solution::Day = parse("0, 0, 0 @ 1, 1, 1\n10, 0, 0 @ -1, 1, 2\n");
+    let skew = skew.with_area(0, 10);
+    assert_eq!(skew.part1(), Answer::Unsigned(1));
+    assert_eq!(skew.with_plane(Plane::XYZ).part1(), Answer::Unsigned(0));
+}
+
+#[test]
+fn day24_example_rock() {
+    let day: day24::solution::Day = parse(
+        "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+",
+    );
+
+    assert_eq!(day.find_rock(), Some(([24, 13, 10], [-3, 1, 2])));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(47))));
+}
+
+#[test]
+fn day24_rock_faster_than_the_search() {
+    let mut day: day24::solution::Day = parse(
+        "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+",
+    );
+    day.configure("max-speed", "2").unwrap();
+    assert_eq!(day.find_rock(), None);
+    assert!(day.solve2().is_err_and(|err| err.contains("from -2 to 2")));
+
+    assert!(day.configure("max-speed", "-1").is_err());
+    day.configure("max-speed", "3").unwrap();
+    assert_eq!(day.solve2(), Ok(47));
+}
+
+#[test]
+fn day24_rock_with_large_coordinates() {
+    let day: day24::solution::Day = parse(
+        "\
+-29755454995041, 605111437068715, 477183296340296 @ 266, -351, -326
+293302409195622, 473785606989281, 181668511749101 @ -304, -26, 196
+270814518329709, 473084069000500, 270025047386540 @ -362, -312, 44
+206576933068791, 383974895452586, 241560822631038 @ -154, -308, 164
+151498982096286, 373604436530891, 298045160495035 @ 179, -274, -172
+",
+    );
+
+    let (position, velocity) = day.find_rock().unwrap();
+    assert_eq!(position, [212345678901234, 287654321098765, 254321987654321]);
+    assert_eq!(velocity, [-187, 243, 91]);
+    assert_eq!(day.solve2(), Ok(754321987654320));
+}
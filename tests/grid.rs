@@ -0,0 +1,51 @@
+use advent_of_code_2023::grid::{Direction, Grid, Pos};
+
+fn grid() -> Grid<char> {
+    "abc\ndef\n".parse().unwrap()
+}
+
+#[test]
+fn parses_rows_of_text() {
+    let grid = grid();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Pos::new(1, 2)], 'f');
+    assert_eq!(grid.get(Pos::new(2, 0)), None);
+    assert_eq!(grid.to_string(), "abc\ndef\n");
+    assert!("abc\nde\n".parse::<Grid<char>>().is_err());
+    assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), Err(1));
+}
+
+#[test]
+fn neighbours_stay_inside() {
+    let grid = grid();
+
+    let corner: Vec<Pos> = grid.neighbours4(Pos::new(0, 0)).collect();
+    assert_eq!(corner, vec![Pos::new(1, 0), Pos::new(0, 1)]);
+    assert_eq!(grid.neighbours8(Pos::new(0, 0)).count(), 3);
+    assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 5);
+    assert_eq!(grid.step(Pos::new(0, 2), Direction::Right), None);
+    assert_eq!(grid.step(Pos::new(0, 2), Direction::Down), Some(Pos::new(1, 2)));
+}
+
+#[test]
+fn views() {
+    let grid = grid();
+
+    assert_eq!(grid.row(1), ['d', 'e', 'f']);
+    assert_eq!(grid.column(1).collect::<String>(), "be");
+    assert_eq!(grid.rows().count(), 2);
+    let region: String = grid.region(Pos::new(0, 1), Pos::new(5, 5)).map(|(_, c)| c).collect();
+    assert_eq!(region, "bcef");
+    assert_eq!(grid.find(|&c| c == 'e'), Some(Pos::new(1, 1)));
+}
+
+#[test]
+fn transposes_and_rotates() {
+    let grid = grid();
+
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+    assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+    assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad\n");
+    assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+}
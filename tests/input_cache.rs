@@ -0,0 +1,73 @@
+use advent_of_code_2023::inputs::{Fetcher, InputManager, LocalFetcher, SessionFetcher};
+use std::cell::Cell;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+// Stands in for adventofcode.com and counts the downloads.
+struct MockFetcher {
+    calls: Rc<Cell<usize>>,
+}
+
+impl Fetcher for MockFetcher {
+    fn user(&self) -> String {
+        "mock".to_string()
+    }
+
+    fn fetch(&self, year: u32, day: u32) -> Result<Vec<u8>, String> {
+        self.calls.set(self.calls.get() + 1);
+        if day > 25 {
+            return Err(format!("404 for {}/day/{}", year, day));
+        }
+        Ok(format!("input of {} day {}\n", year, day).into_bytes())
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn fetches_once_then_reads_the_cache() {
+    let dir = temp_dir("cache");
+    let calls = Rc::new(Cell::new(0));
+    let manager = InputManager::new(&dir, Box::new(MockFetcher { calls: calls.clone() }));
+
+    let path = manager.resolve(3).unwrap();
+    assert_eq!(path, dir.join("2023").join("mock").join("day3.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "input of 2023 day 3\n");
+    assert_eq!(manager.resolve(3).unwrap(), path);
+    assert_eq!(calls.get(), 1);
+
+    let other_year = InputManager::new(&dir, Box::new(MockFetcher { calls: calls.clone() })).with_year(2022);
+    assert_eq!(fs::read_to_string(other_year.resolve(3).unwrap()).unwrap(), "input of 2022 day 3\n");
+    assert_eq!(calls.get(), 2);
+
+    assert!(manager.resolve(26).is_err());
+    assert!(!manager.cache_path(26).exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn local_fetcher_inputs_are_not_cached() {
+    let dir = temp_dir("local");
+    let src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+    let manager = InputManager::new(&dir, Box::new(LocalFetcher::new(&src)));
+
+    // The bundled file itself, so later edits to it are picked up.
+    assert_eq!(manager.resolve(6).unwrap(), src.join("day6").join("input.txt"));
+    assert!(!dir.exists());
+}
+
+#[test]
+fn session_user_does_not_contain_the_token() {
+    let fetcher = SessionFetcher::new("53616c7465645f5f\n").with_base_url("http://localhost:8080/");
+
+    assert_eq!(fetcher.url(2023, 5), "http://localhost:8080/2023/day/5/input");
+    assert_eq!(fetcher.user().len(), 16);
+    assert!(!fetcher.user().contains("53616c7465645f5f"));
+    assert_eq!(fetcher.user(), SessionFetcher::new("53616c7465645f5f").user());
+}
@@ -0,0 +1,115 @@
+use advent_of_code_2023::interval::{intersect_ranges, merge_ranges, IntervalMap, Segment};
+
+// The seed-to-soil map of the day 5 example.
+fn seed_to_soil() -> IntervalMap {
+    let mut map = IntervalMap::new();
+    map.insert(50, 98, 2).unwrap();
+    map.insert(52, 50, 48).unwrap();
+    map
+}
+
+#[test]
+fn applies_to_points() {
+    let map = seed_to_soil();
+
+    assert_eq!(map.apply(79), 81);
+    assert_eq!(map.apply(14), 14);
+    assert_eq!(map.apply(98), 50);
+    assert_eq!(map.apply(100), 100);
+    assert_eq!(
+        map.segments(),
+        [Segment { start: 50, end: 98, offset: 2 }, Segment { start: 98, end: 100, offset: -48 }]
+    );
+}
+
+#[test]
+fn rejects_overlapping_segments() {
+    let mut map = seed_to_soil();
+
+    assert!(map.insert(0, 97, 2).is_err());
+    assert!(map.insert(0, u64::MAX - 1, 5).is_err());
+    assert!(map.insert(0, 10, 40).is_ok());
+}
+
+#[test]
+fn applies_to_ranges() {
+    let map = seed_to_soil();
+
+    assert_eq!(map.apply_ranges(&[79..93, 55..68]), vec![57..70, 81..95]);
+    assert_eq!(map.apply_ranges(&[95..100, 100..102]), vec![50..52, 97..102]);
+    assert_eq!(merge_ranges(vec![5..7, 1..3, 2..4, 4..4]), vec![1..4, 5..7]);
+}
+
+#[test]
+fn fills_gaps_with_identity() {
+    let filled = seed_to_soil().fill_gaps();
+
+    assert_eq!(filled.segments().len(), 4);
+    assert_eq!(filled.segments()[0], Segment { start: 0, end: 50, offset: 0 });
+    assert_eq!(filled.segments()[3], Segment { start: 100, end: u64::MAX, offset: 0 });
+    assert_eq!(filled.apply(79), 81);
+}
+
+#[test]
+fn composes_maps() {
+    let mut soil_to_fertilizer = IntervalMap::new();
+    soil_to_fertilizer.insert(0, 15, 37).unwrap();
+    soil_to_fertilizer.insert(37, 52, 2).unwrap();
+    soil_to_fertilizer.insert(39, 0, 15).unwrap();
+
+    let composed = seed_to_soil().then(&soil_to_fertilizer).unwrap();
+    for seed in 0..120 {
+        assert_eq!(composed.apply(seed), soil_to_fertilizer.apply(seed_to_soil().apply(seed)), "seed {}", seed);
+    }
+    assert_eq!(composed.apply(79), 81);
+    assert_eq!(composed.apply(14), 53);
+    assert_eq!(composed.apply(u64::MAX), u64::MAX);
+}
+
+#[test]
+fn rejects_shifts_too_far_for_an_offset() {
+    let mut far = IntervalMap::new();
+    far.insert(i64::MAX as u64, 0, 10).unwrap();
+    let mut further = IntervalMap::new();
+    further.insert(i64::MAX as u64 + 100, i64::MAX as u64, 10).unwrap();
+
+    assert!(far.then(&further).is_err_and(|err| err.contains("0..10")));
+    assert!(further.then(&far).is_ok());
+    assert!(IntervalMap::new().insert(0, 1 << 63, 1).is_err());
+}
+
+#[test]
+fn inverts_bijections_only() {
+    let map = seed_to_soil();
+    let inverse = map.invert().unwrap();
+    for seed in 0..120 {
+        assert_eq!(inverse.apply(map.apply(seed)), seed);
+    }
+    assert_eq!(map.then(&inverse), Ok(IntervalMap::new()));
+
+    let mut squash = IntervalMap::new();
+    squash.insert(0, 10, 5).unwrap();
+    assert_eq!(squash.invert(), None);
+}
+
+#[test]
+fn preimage_collects_every_source() {
+    let map = seed_to_soil();
+    let sources = map.preimage(&[50..53, 99..101]);
+    assert_eq!(sources, vec![50..51, 97..101]);
+    for seed in 0..200 {
+        let hit = [50..53, 99..101].iter().any(|range| range.contains(&map.apply(seed)));
+        assert_eq!(sources.iter().any(|range| range.contains(&seed)), hit, "seed {}", seed);
+    }
+
+    // Without an inverse, both ways into 0..3 show up.
+    let mut squash = IntervalMap::new();
+    squash.insert(0, 10, 5).unwrap();
+    assert_eq!(squash.preimage(&[0..3, 100..100]), vec![0..3, 10..13]);
+}
+
+#[test]
+fn intersects_range_lists() {
+    assert_eq!(intersect_ranges(&[0..10, 20..30], &[5..25, 40..50]), vec![5..10, 20..25]);
+    assert_eq!(intersect_ranges(&[0..10, 30..40], &[10..20, 40..50]), Vec::<std::ops::Range<u64>>::new());
+}
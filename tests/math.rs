@@ -0,0 +1,41 @@
+use advent_of_code_2023::math::{gcd, lcm, Rational};
+
+#[test]
+fn gcd_and_lcm() {
+    assert_eq!(gcd(12, 18), 6);
+    assert_eq!(lcm(4, 6), 12);
+}
+
+#[test]
+fn rationals_are_normalized() {
+    assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+    assert_eq!(Rational::new(-3, 2).denominator(), 2);
+    assert_eq!(Rational::new(0, -7), Rational::ZERO);
+    assert_eq!(Rational::new(10, 5).to_integer(), Some(2));
+    assert_eq!(Rational::new(-3, 2).to_string(), "-3/2");
+    assert_eq!(Rational::checked_new(1, 0), None);
+}
+
+#[test]
+fn rational_arithmetic_is_exact() {
+    let third = Rational::new(1, 3);
+    let sixth = Rational::new(1, 6);
+
+    assert_eq!(third + sixth, Rational::new(1, 2));
+    assert_eq!(third - sixth, sixth);
+    assert_eq!(third * sixth, Rational::new(1, 18));
+    assert_eq!(third / sixth, Rational::from(2i128));
+    assert_eq!(-third, Rational::new(-1, 3));
+    assert_eq!(third.checked_div(Rational::ZERO), None);
+    assert_eq!(Rational::from(i128::MAX).checked_add(Rational::from(1i128)), None);
+
+    // 0.1 + 0.2 without the floating point surprise.
+    assert_eq!(Rational::new(1, 10) + Rational::new(2, 10), Rational::new(3, 10));
+}
+
+#[test]
+fn rationals_compare_by_value() {
+    assert!(Rational::new(1, 3) < Rational::new(1, 2));
+    assert!(Rational::new(-1, 2) < Rational::new(-1, 3));
+    assert!(Rational::new(400000000000001, 2) > Rational::from(200000000000000i128));
+}
@@ -0,0 +1,101 @@
+// Answers for the bundled `src/dayN/input.txt` files, recorded so refactors cannot change them silently.
+use advent_of_code_2023::solution::{Answer, Solution};
+use advent_of_code_2023::{day1, day10, day2, day24, day3, day4, day5, day6, day7, day8, day9};
+
+fn parse<S: Solution>(day: u32) -> S {
+    let input_file = format!("{}/src/day{}/input.txt", env!("CARGO_MANIFEST_DIR"), day);
+    match S::parse(&input_file) {
+        Ok(day) => day,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+#[test]
+fn day1_input() {
+    let day: day1::solution::Day = parse(1);
+
+    assert_eq!(day.part1(), Answer::Unsigned(54561));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(54076))));
+}
+
+#[test]
+fn day2_input() {
+    let day: day2::solution::Day = parse(2);
+
+    assert_eq!(day.part1(), Answer::Unsigned(2716));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(72227))));
+}
+
+#[test]
+fn day3_input() {
+    let day: day3::solution::Day = parse(3);
+
+    assert_eq!(day.part1(), Answer::Unsigned(519444));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(74528807))));
+}
+
+#[test]
+fn day4_input() {
+    let day: day4::solution::Day = parse(4);
+
+    assert_eq!(day.part1(), Answer::Signed(24160));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(5659035))));
+}
+
+#[test]
+fn day5_input() {
+    let day: day5::solution::Day = parse(5);
+
+    assert_eq!(day.part1(), Answer::Signed(313045984));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(20283860))));
+}
+
+#[test]
+fn day6_input() {
+    let day: day6::solution::Day = parse(6);
+
+    assert_eq!(day.part1(), Answer::Signed(74698));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(27563421))));
+}
+
+#[test]
+fn day7_input() {
+    let day: day7::solution::Day = parse(7);
+
+    assert_eq!(day.part1(), Answer::Unsigned(250254244));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(250087440))));
+}
+
+#[test]
+fn day8_input() {
+    let day: day8::solution::Day = parse(8);
+
+    assert_eq!(day.part1(), Answer::Unsigned(22411));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(11188774513823))));
+}
+
+#[test]
+fn day9_input() {
+    let day: day9::solution::Day = parse(9);
+
+    assert_eq!(day.part1(), Answer::Signed(2075724761));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(1072))));
+}
+
+#[test]
+fn day10_input() {
+    let day: day10::solution::Day = parse(10);
+
+    assert_eq!(day.part1(), Answer::Unsigned(6846));
+    assert_eq!(day.part2(), Some(Ok(Answer::Unsigned(325))));
+    assert_eq!(day.solve2_shoelace(), 325);
+}
+
+#[test]
+fn day24_input() {
+    // The bundled file is the puzzle example, which has no crossings inside the real test area.
+    let day: day24::solution::Day = parse(24);
+
+    assert_eq!(day.part1(), Answer::Unsigned(0));
+    assert_eq!(day.part2(), Some(Ok(Answer::Signed(47))));
+}
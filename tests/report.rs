@@ -0,0 +1,93 @@
+use advent_of_code_2023::answers::Verdict;
+use advent_of_code_2023::report::{render, Format};
+use advent_of_code_2023::runner::PartResult;
+use advent_of_code_2023::solution::{Answer, Failure};
+use std::time::Duration;
+
+fn results() -> Vec<PartResult> {
+    vec![
+        PartResult {
+            day: 24,
+            part: 1,
+            variant: None,
+            answer: Some(Answer::Unsigned(2)),
+            failure: None,
+            input: "in \"quotes\", too".to_string(),
+            input_hash: "0123456789abcdef".to_string(),
+            verdict: Verdict::Mismatch("13135 was too low".to_string()),
+            parse_time: Duration::from_nanos(1500),
+            time: Duration::from_nanos(250),
+        },
+        PartResult {
+            day: 24,
+            part: 2,
+            variant: Some("bruteforce"),
+            answer: None,
+            failure: None,
+            input: "input.txt".to_string(),
+            input_hash: "0123456789abcdef".to_string(),
+            verdict: Verdict::Unknown,
+            parse_time: Duration::from_nanos(1500),
+            time: Duration::ZERO,
+        },
+        PartResult {
+            day: 24,
+            part: 2,
+            variant: None,
+            answer: None,
+            failure: Some(Failure::Failed("no rock".to_string())),
+            input: "input.txt".to_string(),
+            input_hash: "0123456789abcdef".to_string(),
+            verdict: Verdict::Unknown,
+            parse_time: Duration::from_nanos(1500),
+            time: Duration::from_nanos(10),
+        },
+    ]
+}
+
+#[test]
+fn formats_parse() {
+    assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+    assert_eq!("csv".parse::<Format>(), Ok(Format::Csv));
+    assert_eq!("table".parse::<Format>(), Ok(Format::Table));
+    assert!("xml".parse::<Format>().is_err());
+}
+
+#[test]
+fn json_output() {
+    let expected = concat!(
+        "[\n",
+        "  {\"day\": 24, \"part\": 1, \"variant\": null, \"answer\": 2, \"status\": \"MISMATCH\", \"detail\": \"13135 was too low\", ",
+        "\"parse_ns\": 1500, \"time_ns\": 250, \"input\": \"in \\\"quotes\\\", too\", \"input_hash\": \"0123456789abcdef\"},\n",
+        "  {\"day\": 24, \"part\": 2, \"variant\": \"bruteforce\", \"answer\": null, \"status\": \"UNKNOWN\", \"detail\": \"\", ",
+        "\"parse_ns\": 1500, \"time_ns\": 0, \"input\": \"input.txt\", \"input_hash\": \"0123456789abcdef\"},\n",
+        "  {\"day\": 24, \"part\": 2, \"variant\": null, \"answer\": null, \"status\": \"FAILED\", \"detail\": \"no rock\", ",
+        "\"parse_ns\": 1500, \"time_ns\": 10, \"input\": \"input.txt\", \"input_hash\": \"0123456789abcdef\"}\n",
+        "]\n",
+    );
+    assert_eq!(render(&results(), Format::Json), expected);
+    assert_eq!(render(&[], Format::Json), "[]\n");
+}
+
+#[test]
+fn csv_output() {
+    let expected = concat!(
+        "day,part,variant,answer,status,detail,parse_ns,time_ns,input,input_hash\n",
+        "24,1,,2,MISMATCH,13135 was too low,1500,250,\"in \"\"quotes\"\", too\",0123456789abcdef\n",
+        "24,2,bruteforce,,UNKNOWN,,1500,0,input.txt,0123456789abcdef\n",
+        "24,2,,,FAILED,no rock,1500,10,input.txt,0123456789abcdef\n",
+    );
+    assert_eq!(render(&results(), Format::Csv), expected);
+}
+
+#[test]
+fn table_output() {
+    let table = render(&results(), Format::Table);
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("day  part            answer  status"));
+    assert!(lines[1].contains("MISMATCH (13135 was too low)"));
+    assert!(lines[2].starts_with("24   2 (bruteforce)  -       not implemented"));
+    assert!(lines[3].starts_with("24   2               -       FAILED (no rock)"));
+}
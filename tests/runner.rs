@@ -0,0 +1,188 @@
+use advent_of_code_2023::answers::{input_hash, AnswerStore, Verdict};
+use advent_of_code_2023::bench::BenchResult;
+use advent_of_code_2023::runner::{bench_entry, find, run_entry, Args};
+use advent_of_code_2023::solution::{Answer, Failure};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("aoc-runner-{}-{}", std::process::id(), name));
+    fs::write(&path, content).expect("temp dir should be writable");
+    path
+}
+
+fn args(args: &[&str]) -> Result<Args, String> {
+    Args::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn marks_need_one_day_and_one_part() {
+    assert!(args(&["1", "--accept=12"]).is_err_and(|err| err.contains("--part 1 or --part 2")));
+    assert!(args(&["1", "--part", "both", "--accept=12"]).is_err());
+    assert!(args(&["--all", "--part", "1", "--accept=12"]).is_err_and(|err| err.contains("single day")));
+    assert!(args(&["1", "--part", "2", "--accept=12"]).is_ok());
+}
+
+#[test]
+fn mark_lands_on_the_given_part_only() {
+    let input = temp_file("marks.txt", "a1b2\nc3d4\n");
+    let answers = temp_file("marks.toml", "");
+    let (input, answers) = (input.display().to_string(), answers.display().to_string());
+    let args = args(&["1", "--input", &input, "--part", "2", "--accept=12", "--answers", &answers]).unwrap();
+
+    let mut store = AnswerStore::load(&answers).unwrap();
+    run_entry(find(1).unwrap(), &input, &args, &mut store).unwrap();
+    let hash = input_hash(&fs::read(&input).unwrap());
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&answers).unwrap();
+
+    assert_eq!(store.check(1, 2, &hash, Answer::Unsigned(12)), Verdict::Match);
+    assert_eq!(store.check(1, 1, &hash, Answer::Unsigned(12)), Verdict::Unknown);
+    assert!(!store.to_toml().contains("[day1.part1."));
+}
+
+#[test]
+fn failing_part_keeps_the_other_parts() {
+    let input = temp_file("rock.txt", "19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1, -2\n20, 25, 34 @ -2, -2, -4\n");
+    let answers = temp_file("rock.toml", "");
+    let (input, answers) = (input.display().to_string(), answers.display().to_string());
+    let args = args(&["24", "--input", &input, "--opt", "max-speed=2", "--answers", &answers]).unwrap();
+
+    let mut store = AnswerStore::load(&answers).unwrap();
+    let results = run_entry(find(24).unwrap(), &input, &args, &mut store).unwrap();
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&answers).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!((results[0].answer, &results[0].failure), (Some(Answer::Unsigned(0)), &None));
+    assert_eq!(results[1].answer, None);
+    assert!(matches!(&results[1].failure, Some(Failure::Failed(reason)) if reason.contains("from -2 to 2")));
+}
+
+#[test]
+fn settings_need_one_day() {
+    assert!(args(&["--all", "--opt", "threads=2"]).is_err_and(|err| err.contains("single day")));
+    assert!(args(&["5", "--opt", "threads=2"]).is_ok());
+}
+
+#[test]
+fn only_settings_that_change_the_puzzle_skip_the_check() {
+    let input = temp_file("verdicts.txt", "19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1, -2\n20, 25, 34 @ -2, -2, -4\n12, 31, 28 @ -1, -2, -1\n20, 19, 15 @ 1, -5, -3\n");
+    let answers = temp_file("verdicts.toml", "");
+    let (input, answers) = (input.display().to_string(), answers.display().to_string());
+    let run = |settings: &[&str], store: &mut AnswerStore| {
+        let mut all = vec!["24", "--input", &input, "--part", "2", "--answers", &answers];
+        all.extend(settings);
+        run_entry(find(24).unwrap(), &input, &args(&all).unwrap(), store)
+    };
+
+    let mut store = AnswerStore::load(&answers).unwrap();
+    run(&["--opt", "max-speed=5", "--accept"], &mut store).unwrap();
+    let kept = run(&["--opt", "max-speed=5"], &mut store).unwrap();
+    let changed = run(&["--opt", "area=7..27"], &mut store).unwrap();
+    let marked = run(&["--opt", "area=7..27", "--accept"], &mut store);
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&answers).unwrap();
+
+    assert_eq!(kept[0].verdict, Verdict::Match);
+    assert_eq!(changed[0].verdict, Verdict::Unknown);
+    assert!(marked.is_err_and(|err| err.contains("change them")));
+}
+
+#[test]
+fn report_stays_out_of_json_and_csv() {
+    let input = temp_file("report.txt", "seeds: 79 14 55 13\n\nseed-to-location map:\n50 98 2\n52 50 48\n");
+    let run = |format: &str| {
+        Command::new(env!("CARGO_BIN_EXE_advent-of-code-2023"))
+            .args(["5", "--input", &input.display().to_string(), "--report", "--format", format, "--answers"])
+            .arg(env::temp_dir().join("aoc-runner-report-none.toml"))
+            .output()
+            .unwrap()
+    };
+    let (json, csv, table) = (run("json"), run("csv"), run("table"));
+    fs::remove_file(&input).unwrap();
+
+    let json_out = String::from_utf8(json.stdout).unwrap();
+    assert!(json_out.starts_with('[') && json_out.trim_end().ends_with(']'), "{}", json_out);
+    assert!(String::from_utf8(json.stderr).unwrap().contains("day 5 report:"));
+    assert!(String::from_utf8(csv.stdout).unwrap().starts_with("day,part,"));
+    assert!(String::from_utf8(csv.stderr).unwrap().contains("day 5 report:"));
+    assert!(String::from_utf8(table.stdout).unwrap().contains("day 5 report:"));
+}
+
+#[test]
+fn report_settings_need_the_report() {
+    let input = temp_file("reverse.txt", "seeds: 79 14 55 13\n\nseed-to-location map:\n50 98 2\n52 50 48\n");
+    let answers = temp_file("reverse.toml", "");
+    let (input, answers) = (input.display().to_string(), answers.display().to_string());
+    let run = |report: &[&str]| {
+        let mut all = vec!["5", "--input", &input, "--opt", "reverse=50", "--answers", &answers];
+        all.extend(report);
+        run_entry(find(5).unwrap(), &input, &args(&all).unwrap(), &mut AnswerStore::load(&answers).unwrap())
+    };
+
+    let without = run(&[]);
+    let with = run(&["--report"]);
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&answers).unwrap();
+
+    assert!(without.is_err_and(|err| err.contains("needs --report")));
+    assert!(with.is_ok());
+}
+
+#[test]
+fn bruteforce_is_cancelled_at_the_timeout() {
+    let input = temp_file("timeout.txt", "seeds: 79 300000000\n\nseed-to-location map:\n50 98 2\n52 50 48\n");
+    let answers = temp_file("timeout.toml", "");
+    let (input, answers) = (input.display().to_string(), answers.display().to_string());
+    let args = args(&["5", "--input", &input, "--part", "2", "--variants", "--opt", "timeout=0.01", "--answers", &answers]).unwrap();
+
+    let results = run_entry(find(5).unwrap(), &input, &args, &mut AnswerStore::load(&answers).unwrap()).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2023"))
+        .args(["5", "--input", &input, "--variants", "--opt", "timeout=0.01", "--answers", &answers])
+        .output()
+        .unwrap();
+    fs::remove_file(&input).unwrap();
+    fs::remove_file(&answers).unwrap();
+
+    let table = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(table.lines().count(), 4, "{}", table);
+    assert!(table.contains("CANCELLED (stopped after the 10ms timeout)"), "{}", table);
+
+    assert_eq!(results[0].answer, Some(Answer::Signed(50)));
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].variant, Some("bruteforce"));
+    assert_eq!(results[1].failure, Some(Failure::Cancelled("stopped after the 10ms timeout".to_string())));
+}
+
+#[test]
+fn lines_without_digits_are_warned_about() {
+    let input = temp_file("warn.txt", "two1nine\n\nabc\nx7yfive\nzzz\n");
+    let output = Command::new(env!("CARGO_BIN_EXE_advent-of-code-2023"))
+        .args(["1", "--input", &input.display().to_string(), "--answers"])
+        .arg(env::temp_dir().join("aoc-runner-warn-none.toml"))
+        .output()
+        .unwrap();
+    fs::remove_file(&input).unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.matches("day 1: warning: part 1: line(s) 3, 5 have no digit").count(), 1, "{}", stderr);
+    assert_eq!(stderr.matches("day 1: warning: part 2: line(s) 3, 5 have no digit").count(), 1, "{}", stderr);
+    assert!(String::from_utf8(output.stdout).unwrap().contains("88"));
+}
+
+#[test]
+fn bench_leaves_out_parts_without_an_answer() {
+    let input = temp_file("bench.txt", "19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1, -1, -2\n20, 25, 34 @ -2, -2, -4\n");
+    let path = input.display().to_string();
+    let failing = bench_entry(find(24).unwrap(), &path, &args(&["24", "--opt", "max-speed=2"]).unwrap(), 2);
+    let passing = bench_entry(find(24).unwrap(), &path, &args(&["24"]).unwrap(), 2);
+    fs::remove_file(&input).unwrap();
+
+    let names = |results: Vec<BenchResult>| results.into_iter().map(|result| result.name).collect::<Vec<_>>();
+    assert_eq!(names(failing.unwrap()), ["parse", "part1"]);
+    assert_eq!(names(passing.unwrap()), ["parse", "part1", "part2"]);
+}
@@ -0,0 +1,53 @@
+use advent_of_code_2023::scaffold::generate;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+#[test]
+fn generates_and_registers_a_day() {
+    let root = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    write(&root, "src/lib.rs", "pub mod day1;\npub mod day24;\npub mod math;\n");
+    write(
+        &root,
+        "src/runner.rs",
+        "use crate::{day1, day24};\n\npub const REGISTRY: &[Entry] = &[\n    Entry { day: 1, load: load::<day1::solution::Day> },\n    Entry { day: 24, load: load::<day24::solution::Day> },\n];\n",
+    );
+    write(
+        &root,
+        "tests/examples.rs",
+        "use advent_of_code_2023::{day1, day24};\n\n#[test]\nfn day1_example() {}\n\n#[test]\nfn day24_example() {}\n",
+    );
+
+    let written = generate(&root, 11).unwrap();
+    assert_eq!(written.len(), 6);
+    assert_eq!(fs::read_to_string(root.join("src/day11/mod.rs")).unwrap(), "pub mod solution;\n");
+    assert!(fs::read_to_string(root.join("src/day11/solution.rs")).unwrap().contains("pub fn solve2(&self) -> u64"));
+    assert_eq!(fs::read_to_string(root.join("src/day11/input.txt")).unwrap(), "");
+    assert_eq!(
+        fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+        "pub mod day1;\npub mod day11;\npub mod day24;\npub mod math;\n"
+    );
+
+    let runner = fs::read_to_string(root.join("src/runner.rs")).unwrap();
+    assert!(runner.starts_with("use crate::{day1, day11, day24};\n"));
+    assert!(runner.contains(
+        "day1::solution::Day> },\n    Entry { day: 11, load: load::<day11::solution::Day> },\n    Entry { day: 24,"
+    ));
+
+    let examples = fs::read_to_string(root.join("tests/examples.rs")).unwrap();
+    assert!(examples.starts_with("use advent_of_code_2023::{day1, day11, day24};\n"));
+    let stub = examples.find("fn day11_example()").unwrap();
+    assert!(examples.find("fn day1_example()").unwrap() < stub);
+    assert!(stub < examples.find("fn day24_example()").unwrap());
+
+    assert!(generate(&root, 11).unwrap_err().contains("already exists"));
+    assert!(generate(&root, 26).is_err());
+    fs::remove_dir_all(&root).unwrap();
+}